@@ -1,19 +1,63 @@
 # frozen_string_literal: true
 
 require 'base64'
+require 'delegate'
 require 'json'
 require 'logger'
 require 'net/http'
 require 'openssl'
+require 'strscan'
+require 'securerandom'
+require 'stringio'
+require 'time'
+require 'zlib'
 
 require 'net/hippie/version'
-require 'net/hippie/client'
+require 'net/hippie/api_type_detector'
+require 'net/hippie/assertions'
+require 'net/hippie/authenticator'
+require 'net/hippie/aws_sigv4'
+require 'net/hippie/cache_invalidation'
+require 'net/hippie/certificate_transparency'
+require 'net/hippie/chunked_body'
 require 'net/hippie/connection'
+require 'net/hippie/content_digest'
 require 'net/hippie/content_type_mapper'
+require 'net/hippie/curl_command'
+require 'net/hippie/diagnostics'
+require 'net/hippie/downloads'
+require 'net/hippie/fallback_response'
+require 'net/hippie/geo_json'
+require 'net/hippie/headers'
+require 'net/hippie/hooks'
+require 'net/hippie/inflater'
+require 'net/hippie/json_patch'
+require 'net/hippie/multipart_body'
+require 'net/hippie/multipart_mixed'
+require 'net/hippie/net_http_settings'
+require 'net/hippie/problem_details'
+require 'net/hippie/redactor'
+require 'net/hippie/redirected_response'
+require 'net/hippie/responses'
+require 'net/hippie/retries'
+require 'net/hippie/retry_after'
+require 'net/hippie/server_capabilities'
+require 'net/hippie/soap_response'
+require 'net/hippie/structured_fields'
+require 'net/hippie/text_decoder'
+require 'net/hippie/throttle'
+require 'net/hippie/throttled_fetcher'
+require 'net/hippie/uploads'
+require 'net/hippie/vapid_builder'
+require 'net/hippie/client'
 
 module Net
   # net/http for hippies.
   module Hippie
+    extend Assertions
+    extend Headers
+    extend Responses
+
     CONNECTION_ERRORS = [
       EOFError,
       Errno::ECONNREFUSED,
@@ -30,6 +74,65 @@ module Net
       Timeout::Error
     ].freeze
 
+    UNSAFE_URL_CHARACTERS = %r{[^a-zA-Z0-9\-._~:/?#\[\]@!$&'()*+,;=%]}.freeze
+
+    # Client methods whose body comes from a block that cannot be replayed,
+    # so a failed attempt is not retried.
+    UNRETRIED_METHODS = %i[post_chunked].freeze
+
+    # Raised when a redirect would downgrade from https to http.
+    class InsecureRedirect < StandardError; end
+
+    # Raised when a server certificate fails a check made by this library.
+    class SSLError < StandardError; end
+
+    # Raised when a server asks to retry later than `max_retry_after_secs` allows.
+    class RateLimitedError < StandardError; end
+
+    # Raised when a body does not match its Content-Digest header.
+    class IntegrityError < StandardError; end
+
+    # Raised when a resource was last modified longer ago than allowed.
+    class StaleResourceError < StandardError
+      attr_reader :last_modified_at, :max_age_secs
+
+      def initialize(last_modified_at, max_age_secs)
+        @last_modified_at = last_modified_at
+        @max_age_secs = max_age_secs
+        super("Last modified at #{last_modified_at.httpdate}, more than #{max_age_secs}s ago")
+      end
+    end
+
+    # Raised when a response has no Last-Modified header to check.
+    class MissingLastModifiedError < StandardError; end
+
+    # Raised when a JSON Patch cannot be applied to a document.
+    class JsonPatchError < StandardError; end
+
+    # Raised when a response fails one of the `Net::Hippie.assert_*` checks.
+    class AssertionError < StandardError; end
+
+    # Raised when a response is not of the content type a method expects.
+    class UnexpectedContentTypeError < StandardError
+      attr_reader :expected, :actual
+
+      def initialize(expected, actual)
+        @expected = expected
+        @actual = actual
+        super("Expected #{expected} but received #{actual.inspect}")
+      end
+    end
+
+    # Raised when a server answers with an RFC 7807 problem document.
+    class ProblemDetailsError < StandardError
+      attr_reader :problem
+
+      def initialize(problem)
+        @problem = problem
+        super(problem.title || problem.type)
+      end
+    end
+
     def self.logger
       @logger ||= Logger.new($stderr)
     end
@@ -54,11 +157,36 @@ module Net
       "Bearer #{token}"
     end
 
-    def self.method_missing(symbol, *args)
-      default_client.with_retry(retries: 3) do |client|
-        client.public_send(symbol, *args)
-      end || super
+    # Resolves `relative` against `base` following RFC 3986 section 5.
+    def self.resolve_url(base, relative)
+      URI.join(normalize_url(base), normalize_url(relative)).to_s
+    end
+
+    # Percent-encodes characters that are not allowed in a URI and
+    # lowercases the scheme and host.
+    def self.normalize_url(url)
+      encoded = url.to_s.gsub(UNSAFE_URL_CHARACTERS) do |x|
+        x.unpack('C*').map { |byte| format('%%%02X', byte) }.join
+      end
+      uri = URI.parse(encoded)
+      uri.scheme = uri.scheme.downcase if uri.scheme
+      uri.host = uri.host.downcase if uri.host
+      uri.to_s
+    end
+
+    # Applies RFC 6902 JSON Patch operations to a copy of `document`.
+    def self.apply_json_patch(document, operations)
+      JsonPatch.new(operations).apply(document)
+    end
+
+    def self.method_missing(symbol, *args, &block)
+      return super unless respond_to_missing?(symbol)
+
+      default_client.with_retry(retries: UNRETRIED_METHODS.include?(symbol) ? 0 : 3) do |client|
+        client.public_send(symbol, *args, &block)
+      end
     end
+    singleton_class.send(:ruby2_keywords, :method_missing) if singleton_class.respond_to?(:ruby2_keywords, true)
 
     def self.respond_to_missing?(name, _include_private = false)
       Client.public_instance_methods.include?(name.to_sym) || super
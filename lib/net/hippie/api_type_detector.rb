@@ -0,0 +1,66 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Guesses the kind of API behind a URL from its path, content type,
+    # allowed methods and the first bytes of its body.
+    class ApiTypeDetector
+      GRAPHQL_PATHS = %w[/graphql /.well-known/graphql].freeze
+      REST_METHODS = %w[PUT PATCH DELETE].freeze
+
+      def detect(uri, response)
+        hint_for(uri, response) || detect_from_body(sample_of(response))
+      end
+
+      # Returns the type suggested by the path or Content-Type header of a
+      # response, such as one to a HEAD request, or nil.
+      def hint_for(uri, response)
+        return :graphql if %r{/graphql/?\z}.match?(URI.parse(uri.to_s).path)
+
+        detect_from_content_type(response['Content-Type'].to_s.downcase, sample_of(response))
+      end
+
+      # A resource that allows PUT, PATCH or DELETE is taken to be a REST API.
+      def hint_from_allow(allow)
+        :json_api if allow.to_s.upcase.split(/\s*,\s*/).any? { |x| REST_METHODS.include?(x) }
+      end
+
+      # Returns true when a response answers a `{__typename}` query.
+      def graphql?(response)
+        return false unless response.is_a?(Net::HTTPSuccess)
+
+        document = JSON.parse(response.body.to_s)
+        document.is_a?(Hash) && document['data'].is_a?(Hash) && document['data'].key?('__typename')
+      rescue JSON::ParserError
+        false
+      end
+
+      private
+
+      def sample_of(response)
+        response.body.to_s.lstrip[0, 512].to_s
+      end
+
+      def detect_from_content_type(content_type, body)
+        return :graphql if content_type.include?('graphql')
+        return :soap if content_type.include?('soap') || (content_type.include?('xml') && soap?(body))
+        return :rss if content_type.match?(/rss|atom/)
+        return :json_api if content_type.include?('json')
+        return :html if content_type.include?('html')
+      end
+
+      def detect_from_body(body)
+        return :json_api if body.start_with?('{', '[')
+        return :soap if soap?(body)
+        return :rss if body.match?(/<(rss|feed)[\s>]/)
+        return :html if body.match?(/\A(<!doctype html|<html)/i)
+
+        :unknown
+      end
+
+      def soap?(body)
+        body.match?(/<(\w+:)?Envelope[\s>]/)
+      end
+    end
+  end
+end
@@ -0,0 +1,44 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Checks that return the response when it passes and raise otherwise, so
+    # they compose: `assert_header(assert_status(response, 200), 'Vary', '*')`.
+    module Assertions
+      def assert_status(response, expected)
+        return response if response.code.to_i == expected.to_i
+
+        raise AssertionError, "Expected status #{expected} but got #{response.code}"
+      end
+
+      # Returns the response when the header matches `expected`, ignoring case.
+      def assert_header(response, name, expected)
+        actual = response[name]
+        return response if actual.to_s.casecmp?(expected.to_s) && !actual.nil?
+
+        raise AssertionError, "Expected header '#{name}' to be '#{expected}' but got '#{actual}'"
+      end
+
+      def assert_header_present(response, name)
+        return response if response.key?(name)
+
+        raise AssertionError, "Expected header '#{name}' to be present"
+      end
+
+      def assert_header_absent(response, name)
+        return response unless response.key?(name)
+
+        raise AssertionError, "Expected header '#{name}' to be absent but got '#{response[name]}'"
+      end
+
+      # Returns the response when its Content-Type starts with `expected`,
+      # ignoring case, and raises an UnexpectedContentTypeError otherwise.
+      def assert_content_type(response, expected)
+        actual = response['Content-Type']
+        return response if actual.to_s.downcase.start_with?(expected.downcase)
+
+        raise UnexpectedContentTypeError.new(expected, actual)
+      end
+    end
+  end
+end
@@ -0,0 +1,103 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Answers WWW-Authenticate challenges using the configured credentials.
+    class Authenticator
+      SCHEMES = %w[Digest Basic].freeze
+      NONCE_COUNT = '00000001'
+      TOKEN = /[!$#%&'*+\-.^_`|~0-9A-Za-z]+/.freeze
+      TOKEN68 = %r{[A-Za-z0-9\-._~+/]+=*}.freeze
+      PARAM = /(#{TOKEN})\s*=\s*("(?:[^"\\]|\\.)*"|[^,\s]*)/.freeze
+
+      def initialize(username: nil, password: nil)
+        @username = username
+        @password = password
+      end
+
+      def authorization_for(method, uri, response)
+        return if @username.nil? || !response.is_a?(Net::HTTPUnauthorized)
+
+        scheme, params = strongest_challenge_in(response)
+        case scheme
+        when 'Digest'
+          digest_auth(method, URI.parse(uri.to_s).request_uri, params)
+        when 'Basic'
+          Net::Hippie.basic_auth(@username, @password)
+        end
+      end
+
+      # Returns a [scheme, params] pair for each challenge, including
+      # several challenges given in a single WWW-Authenticate header. A
+      # token68 credential is returned under the `token68` param.
+      def challenges_in(response)
+        Array(response.get_fields('WWW-Authenticate')).flat_map do |field|
+          scanner = StringScanner.new(field)
+          challenges = []
+          scan_next(scanner, challenges) until scanner.skip(/[\s,]*/) && scanner.eos?
+          challenges
+        end
+      end
+
+      private
+
+      def strongest_challenge_in(response)
+        challenges_in(response)
+          .select { |scheme, params| supported?(scheme, params) }
+          .min_by { |scheme, _| SCHEMES.index(scheme) }
+      end
+
+      # Digest challenges are only answered with MD5 and either no qop or
+      # qop=auth, so others are skipped in favour of a weaker scheme.
+      def supported?(scheme, params)
+        return SCHEMES.include?(scheme) unless scheme == 'Digest'
+
+        params.fetch('algorithm', 'MD5').casecmp?('MD5') && (params['qop'].nil? || auth_qop?(params))
+      end
+
+      def scan_next(scanner, challenges)
+        if challenges.any? && scanner.scan(PARAM)
+          challenges.last[1][scanner[1].downcase] = unquote(scanner[2])
+        elsif scanner.scan(TOKEN)
+          challenges << [scanner.matched.capitalize, token68_in(scanner)]
+        else
+          scanner.getch
+        end
+      end
+
+      def token68_in(scanner)
+        scanner.scan(/\s+(#{TOKEN68})\s*(?=,|\z)/) ? { 'token68' => scanner[1] } : {}
+      end
+
+      def unquote(value)
+        value.start_with?('"') ? value[1..-2].gsub(/\\(.)/, '\1') : value
+      end
+
+      def digest_auth(method, path, params)
+        cnonce = OpenSSL::Random.random_bytes(8).unpack1('H*') if auth_qop?(params)
+        fields = {
+          username: @username, realm: params['realm'], nonce: params['nonce'], uri: path,
+          response: digest_response(method, path, params, cnonce), opaque: params['opaque']
+        }.compact.map { |key, value| %(#{key}="#{value}") }
+        fields += ['qop=auth', "nc=#{NONCE_COUNT}", %(cnonce="#{cnonce}")] if cnonce
+        "Digest #{fields.join(', ')}"
+      end
+
+      def auth_qop?(params)
+        params['qop'].to_s.split(',').map(&:strip).include?('auth')
+      end
+
+      def digest_response(method, path, params, cnonce)
+        ha1 = md5(@username, params['realm'], @password)
+        ha2 = md5(method, path)
+        return md5(ha1, params['nonce'], ha2) if cnonce.nil?
+
+        md5(ha1, params['nonce'], NONCE_COUNT, cnonce, 'auth', ha2)
+      end
+
+      def md5(*parts)
+        OpenSSL::Digest::MD5.hexdigest(parts.join(':'))
+      end
+    end
+  end
+end
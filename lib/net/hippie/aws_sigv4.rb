@@ -0,0 +1,104 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Signs requests using AWS Signature Version 4.
+    class AwsSigv4
+      ALGORITHM = 'AWS4-HMAC-SHA256'
+      UNSIGNED_PAYLOAD = 'UNSIGNED-PAYLOAD'
+      UNRESERVED = /[^A-Za-z0-9\-_.~]/.freeze
+
+      attr_reader :region, :service, :access_key_id, :session_token
+
+      def initialize(region:, service:, access_key_id:, secret_access_key:, session_token: nil)
+        @region = region
+        @service = service
+        @access_key_id = access_key_id
+        @secret_access_key = secret_access_key
+        @session_token = session_token
+      end
+
+      def sign(request, now = Time.now)
+        request['x-amz-date'] = now.utc.strftime('%Y%m%dT%H%M%SZ')
+        request['x-amz-content-sha256'] = payload_hash_for(request)
+        request['x-amz-security-token'] = session_token if session_token
+        request['Authorization'] = authorization_for(request)
+        request
+      end
+
+      def authorization_for(request)
+        headers = headers_to_sign(request)
+        scope = scope_for(request['x-amz-date'])
+        [
+          "#{ALGORITHM} Credential=#{access_key_id}/#{scope}",
+          "SignedHeaders=#{headers.keys.join(';')}",
+          "Signature=#{signature_for(request, headers, scope)}"
+        ].join(', ')
+      end
+
+      private
+
+      def signature_for(request, headers, scope)
+        string_to_sign = [
+          ALGORITHM,
+          request['x-amz-date'],
+          scope,
+          hexdigest(canonical_request_for(request, headers))
+        ].join("\n")
+        OpenSSL::HMAC.hexdigest('SHA256', signing_key_for(scope), string_to_sign)
+      end
+
+      def canonical_request_for(request, headers)
+        path, query = request.path.split('?', 2)
+        [
+          request.method,
+          path,
+          canonical_query_for(query),
+          headers.map { |name, value| "#{name}:#{value}\n" }.join,
+          headers.keys.join(';'),
+          request['x-amz-content-sha256'] || hexdigest(request.body.to_s)
+        ].join("\n")
+      end
+
+      # A streamed body is only read as it is sent, so it is left unsigned.
+      def payload_hash_for(request)
+        request.body_stream ? UNSIGNED_PAYLOAD : hexdigest(request.body.to_s)
+      end
+
+      def canonical_query_for(query)
+        return '' if query.nil? || query.empty?
+
+        URI.decode_www_form(query)
+          .map { |key, value| "#{escape(key)}=#{escape(value)}" }
+          .sort
+          .join('&')
+      end
+
+      def headers_to_sign(request)
+        request.each_header
+          .select { |name, _| name == 'host' || name.start_with?('x-amz-') }
+          .map { |name, value| [name, value.strip.squeeze(' ')] }
+          .sort
+          .to_h
+      end
+
+      def scope_for(timestamp)
+        "#{timestamp[0, 8]}/#{region}/#{service}/aws4_request"
+      end
+
+      def signing_key_for(scope)
+        scope.split('/').inject("AWS4#{@secret_access_key}") do |key, part|
+          OpenSSL::HMAC.digest('SHA256', key, part)
+        end
+      end
+
+      def escape(value)
+        value.to_s.gsub(UNRESERVED) { |x| x.unpack('C*').map { |y| format('%%%02X', y) }.join }
+      end
+
+      def hexdigest(value)
+        OpenSSL::Digest::SHA256.hexdigest(value)
+      end
+    end
+  end
+end
@@ -0,0 +1,19 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # The PURGE method used by Varnish and Squid to evict a cached URL.
+    class Purge < Net::HTTPRequest
+      METHOD = 'PURGE'
+      REQUEST_HAS_BODY = true
+      RESPONSE_HAS_BODY = true
+    end
+
+    # The BAN method used by Varnish to evict cached URLs matching a rule.
+    class Ban < Net::HTTPRequest
+      METHOD = 'BAN'
+      REQUEST_HAS_BODY = true
+      RESPONSE_HAS_BODY = true
+    end
+  end
+end
@@ -0,0 +1,32 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Rejects server certificates that do not embed Signed Certificate
+    # Timestamps from a Certificate Transparency log. SCTs delivered via the
+    # TLS extension or a stapled OCSP response are not visible through
+    # Ruby's OpenSSL bindings, so only embedded SCTs are accepted.
+    class CertificateTransparency
+      SCT_LIST_OIDS = %w[1.3.6.1.4.1.11129.2.4.2 ct_precert_scts].freeze
+
+      def self.embedded_scts?(certificate)
+        certificate.extensions.any? { |x| SCT_LIST_OIDS.include?(x.oid) }
+      end
+
+      def call(preverified, store_context)
+        return preverified unless preverified && store_context.error_depth.zero?
+
+        @rejected = !self.class.embedded_scts?(store_context.current_cert)
+        !@rejected
+      end
+
+      def rejected?
+        @rejected == true
+      end
+
+      def reset!
+        @rejected = false
+      end
+    end
+  end
+end
@@ -0,0 +1,35 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Adapts a block returning successive chunks, and nil once exhausted, to
+    # the `read` interface Net::HTTP expects of a request body stream.
+    class ChunkedBody
+      def initialize(chunk_size, &block)
+        @chunk_size = chunk_size
+        @source = block
+        @buffer = String.new(encoding: Encoding::BINARY)
+        @eof = false
+      end
+
+      def read(length = nil, outbuf = nil)
+        length = [length, @chunk_size].min if length
+        fill(length)
+        data = @buffer.slice!(0, length || @buffer.bytesize)
+        return if length && data.empty?
+
+        outbuf ? outbuf.replace(data) : data
+      end
+
+      private
+
+      def fill(length)
+        until @eof || (length && @buffer.bytesize >= length)
+          chunk = @source.call
+          @eof = chunk.nil?
+          @buffer << chunk.b unless @eof
+        end
+      end
+    end
+  end
+end
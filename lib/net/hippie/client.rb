@@ -4,6 +4,12 @@ module Net
   module Hippie
     # A simple client for connecting with http resources.
     class Client
+      include Diagnostics
+      include Downloads
+      include Hooks
+      include Retries
+      include Uploads
+
       DEFAULT_HEADERS = {
         'Accept' => 'application/json',
         'Content-Type' => 'application/json',
@@ -12,12 +18,22 @@ module Net
 
       attr_reader :mapper, :logger, :follow_redirects
 
+      # Builds a client with the timeout, TLS and proxy settings of an
+      # existing Net::HTTP instance. Settings without an equivalent are
+      # logged and ignored, and `options` take precedence over the rest.
+      def self.create_from_ruby_net_http_settings(http, options = {})
+        settings = NetHttpSettings.new(http)
+        settings.warn_unsupported(options.fetch(:logger, Net::Hippie.logger))
+        new(settings.to_h.merge(options))
+      end
+
       def initialize(options = {})
         @options = options
         @mapper = options.fetch(:mapper, ContentTypeMapper.new)
         @logger = options.fetch(:logger, Net::Hippie.logger)
         @follow_redirects = options.fetch(:follow_redirects, 0)
         @default_headers = options.fetch(:headers, DEFAULT_HEADERS)
+        @signer = AwsSigv4.new(**options[:aws_sigv4]) if options[:aws_sigv4]
         @connections = Hash.new do |hash, key|
           scheme, host, port = key
           hash[key] = Connection.new(scheme, host, port, options)
@@ -26,8 +42,8 @@ module Net
 
       def execute(uri, request, limit: follow_redirects, &block)
         connection = connection_for(uri)
-        response = connection.run(request)
-        if limit.positive? && response.is_a?(Net::HTTPRedirection)
+        response = dispatch(connection, request)
+        if limit.positive? && follow_redirect?(uri, connection, response)
           url = connection.build_url_for(response['location'])
           request = request_for(Net::HTTP::Get, url)
           execute(url, request, limit: limit - 1, &block)
@@ -56,45 +72,55 @@ module Net
         run(uri, Net::HTTP::Delete, headers, body, &block)
       end
 
-      # attempt 1 -> delay 0.1 second
-      # attempt 2 -> delay 0.2 second
-      # attempt 3 -> delay 0.4 second
-      # attempt 4 -> delay 0.8 second
-      # attempt 5 -> delay 1.6 second
-      # attempt 6 -> delay 3.2 second
-      # attempt 7 -> delay 6.4 second
-      # attempt 8 -> delay 12.8 second
-      def with_retry(retries: 3)
-        retries = 0 if retries.nil? || retries.negative?
-
-        0.upto(retries) do |n|
-          attempt(n, retries) do
-            return yield self
-          end
-        end
+      def purge(uri, headers: {}, body: {}, &block)
+        run(uri, Purge, headers, body, &block)
+      end
+
+      def ban(uri, headers: {}, body: {}, &block)
+        run(uri, Ban, headers, body, &block)
+      end
+
+      def execute_with_tracing_context(span_context, http_method, uri, headers: {}, body: {}, &block)
+        run(uri, http_method, headers.merge(Net::Hippie.trace_context(span_context)), body, &block)
       end
 
       private
 
-      attr_reader :default_headers
+      attr_reader :default_headers, :signer
 
-      def attempt(attempt, max)
-        yield
-      rescue *CONNECTION_ERRORS => error
-        raise error if attempt == max
+      def content_digest
+        return if @options[:content_digest].nil?
 
-        delay = ((2**attempt) * 0.1) + Random.rand(0.05) # delay + jitter
-        logger.warn("`#{error.message}` #{attempt + 1}/#{max} Delay: #{delay}s")
-        sleep delay
+        @content_digest ||= ContentDigest.new(@options[:content_digest])
+      end
+
+      def follow_redirect?(uri, connection, response)
+        return false unless response.is_a?(Net::HTTPRedirection)
+        return true if @options[:allow_insecure_redirects]
+
+        url = connection.build_url_for(response['location'])
+        return true unless URI.parse(uri.to_s).scheme == 'https' && URI.parse(url).scheme == 'http'
+        raise InsecureRedirect, "Refusing to follow redirect to #{url}" if @options[:raise_on_insecure_redirect]
+
+        false
       end
 
       def request_for(type, uri, headers: {}, body: {})
-        final_headers = default_headers.merge(headers)
+        final_headers = default_headers.merge(forwarded_headers_for(uri)).merge(headers)
         type.new(URI.parse(uri.to_s), final_headers).tap do |x|
           x.body = mapper.map_from(final_headers, body) unless body.empty?
+          yield x if block_given?
+          content_digest&.sign(x)
+          signer&.sign(x)
         end
       end
 
+      def forwarded_headers_for(uri)
+        return {} if @options[:forwarded_for].nil?
+
+        Net::Hippie.forwarded_headers(@options[:forwarded_for], uri)
+      end
+
       def run(uri, http_method, headers, body, &block)
         request = request_for(http_method, uri, headers: headers, body: body)
         execute(uri, request, &block)
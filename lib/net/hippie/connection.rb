@@ -5,18 +5,24 @@ module Net
     # A connection to a specific host
     class Connection
       def initialize(scheme, host, port, options = {})
-        http = Net::HTTP.new(host, port)
+        http = Net::HTTP.new(host, port, options.fetch(:proxy_addr, :ENV), options[:proxy_port])
         http.read_timeout = options.fetch(:read_timeout, 10)
         http.open_timeout = options.fetch(:open_timeout, 10)
+        http.write_timeout = options[:write_timeout] if options[:write_timeout] && http.respond_to?(:write_timeout=)
         http.use_ssl = scheme == 'https'
-        http.verify_mode = options.fetch(:verify_mode, Net::Hippie.verify_mode)
-        http.set_debug_output(options.fetch(:logger, Net::Hippie.logger))
+        http.set_debug_output(debug_output_for(options))
+        apply_verification_to(http, options)
         apply_client_tls_to(http, options)
         @http = http
       end
 
       def run(request)
+        @transparency&.reset!
         @http.request(request)
+      rescue OpenSSL::SSL::SSLError
+        raise SSLError, 'Certificate lacks CT proof' if @transparency&.rejected?
+
+        raise
       end
 
       def build_url_for(path)
@@ -27,6 +33,23 @@ module Net
 
       private
 
+      def debug_output_for(options)
+        logger = options.fetch(:logger, Net::Hippie.logger)
+        return logger if logger.nil? || options[:redaction] == false
+
+        Redactor.from(options).wrap(logger)
+      end
+
+      def apply_verification_to(http, options)
+        http.verify_mode = options.fetch(:verify_mode, Net::Hippie.verify_mode)
+        http.ca_file = options[:ca_file] if options[:ca_file]
+        http.verify_hostname = options[:verify_hostname] unless options[:verify_hostname].nil?
+        return unless options[:verify_ct]
+
+        @transparency = CertificateTransparency.new
+        http.verify_callback = @transparency.method(:call)
+      end
+
       def apply_client_tls_to(http, options)
         return if options[:certificate].nil? || options[:key].nil?
 
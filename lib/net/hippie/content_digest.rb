@@ -0,0 +1,51 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Generates and verifies RFC 9530 Content-Digest header values.
+    class ContentDigest
+      ALGORITHMS = {
+        sha256: 'sha-256',
+        sha512: 'sha-512'
+      }.freeze
+
+      def initialize(algorithms = [:sha256])
+        @algorithms = Array(algorithms).map(&:to_sym)
+        unsupported = @algorithms - ALGORITHMS.keys
+        raise ArgumentError, "Unsupported digest: #{unsupported.join(', ')}" if unsupported.any?
+      end
+
+      def sign(request)
+        request['Content-Digest'] = header_for(request.body) if request.body
+        request
+      end
+
+      def header_for(body)
+        @algorithms.map { |x| "#{ALGORITHMS[x]}=:#{digest(x, body)}:" }.join(', ')
+      end
+
+      def verify(response)
+        digests = parse(response['Content-Digest'].to_s)
+        raise IntegrityError, 'Missing Content-Digest' if digests.empty?
+
+        digests.each do |algorithm, expected|
+          raise IntegrityError, "#{ALGORITHMS[algorithm]} mismatch" unless digest(algorithm, response.body) == expected
+        end
+        true
+      end
+
+      private
+
+      def parse(header)
+        header.scan(/([\w-]+)=:([^:]*):/).each_with_object({}) do |(name, value), memo|
+          algorithm = ALGORITHMS.key(name.downcase)
+          memo[algorithm] = value if algorithm
+        end
+      end
+
+      def digest(algorithm, body)
+        ::Base64.strict_encode64(OpenSSL::Digest.digest(algorithm.to_s.upcase, body.to_s))
+      end
+    end
+  end
+end
@@ -0,0 +1,33 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Renders a request as an equivalent curl command.
+    class CurlCommand
+      def initialize(request, redactor = Redactor.new)
+        @request = request
+        @redactor = redactor
+      end
+
+      def to_s
+        parts = ['curl', '-X', @request.method, quote(@redactor.redact_url(@request.uri))]
+        headers.each { |key, value| parts.push('-H', quote("#{key}: #{value}")) }
+        parts.push('--data-raw', quote(@request.body)) if @request.body
+        parts.join(' ')
+      end
+
+      private
+
+      def headers
+        headers = {}
+        @request.each_capitalized { |key, value| headers[key] = value unless key == 'Host' }
+        @redactor.redact_headers(headers)
+      end
+
+      def quote(value)
+        escaped = value.to_s.gsub("'") { %q('\'') }
+        "'#{escaped}'"
+      end
+    end
+  end
+end
@@ -0,0 +1,88 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Client methods that describe a server or a request rather than
+    # fetching a resource for its body.
+    module Diagnostics
+      # Returns :json_api, :graphql, :soap, :rss, :html or :unknown. Checks
+      # the path and content type from a HEAD request, the Allow header of an
+      # OPTIONS request, whether the host answers GraphQL queries and finally
+      # the first 512 bytes of the resource, stopping at the first answer.
+      def detect_api_type(uri, headers: {})
+        detector = ApiTypeDetector.new
+        hint = detector.hint_for(uri, probe(Net::HTTP::Head, uri, headers))
+        hint ||= detector.hint_from_allow(probe(Net::HTTP::Options, uri, headers)['Allow'])
+        hint ||= :graphql if graphql_host?(detector, uri, headers)
+        hint || detector.detect(uri, get(uri, headers: { 'Accept' => '*/*', 'Range' => 'bytes=0-511' }.merge(headers)))
+      end
+
+      # Returns the curl command equivalent to the request this client would
+      # send, without sending it.
+      def debug_request(http_method, uri, headers: {}, body: {}, redact_sensitive: true)
+        request = request_for(http_method, uri, headers: headers, body: body)
+        redactor = redact_sensitive ? Redactor.from(@options) : Redactor.new(headers: [])
+        CurlCommand.new(request, redactor).to_s
+      end
+
+      # Sends a HEAD request, without following redirects, and returns :ok,
+      # :redirect, :forbidden, :not_found, :client_error, :server_error or
+      # :unknown for the response, or :error when there is none. Never raises.
+      def head_check(uri, headers: {})
+        status_of(execute(uri, request_for(Net::HTTP::Head, uri, headers: headers), limit: 0))
+      rescue StandardError
+        :error
+      end
+
+      # Sends a HEAD request and describes the server from its response headers.
+      def introspect_server(uri)
+        headers = { 'Accept-Encoding' => 'gzip, deflate, br' }
+        response = execute(uri, request_for(Net::HTTP::Head, uri, headers: headers))
+        ServerCapabilities.new(response).to_h
+      end
+
+      # Retries a GET once with credentials from the `auth` option when the
+      # server answers 401 with a supported WWW-Authenticate challenge.
+      def negotiate_auth(uri, headers: {})
+        response = get(uri, headers: headers)
+        authorization = authenticator.authorization_for('GET', uri, response)
+        return response if authorization.nil?
+
+        get(uri, headers: headers.merge('Authorization' => authorization))
+      end
+
+      def auth_schemes_supported
+        Authenticator::SCHEMES
+      end
+
+      private
+
+      def status_of(response)
+        case response
+        when Net::HTTPSuccess then :ok
+        when Net::HTTPRedirection then :redirect
+        when Net::HTTPUnauthorized, Net::HTTPForbidden then :forbidden
+        when Net::HTTPNotFound then :not_found
+        when Net::HTTPClientError then :client_error
+        when Net::HTTPServerError then :server_error
+        else :unknown
+        end
+      end
+
+      def probe(type, uri, headers)
+        execute(uri, request_for(type, uri, headers: headers))
+      end
+
+      def graphql_host?(detector, uri, headers)
+        ApiTypeDetector::GRAPHQL_PATHS.any? do |path|
+          url = URI.join(uri.to_s, "#{path}?query=%7B__typename%7D")
+          detector.graphql?(get(url, headers: { 'Accept' => 'application/json' }.merge(headers)))
+        end
+      end
+
+      def authenticator
+        @authenticator ||= Authenticator.new(**@options.fetch(:auth, {}))
+      end
+    end
+  end
+end
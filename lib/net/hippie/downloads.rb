@@ -0,0 +1,116 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Client methods that fetch a resource and decode or check its body.
+    module Downloads
+      # Parses a CSV response one row at a time. Rows are hashes keyed by the
+      # header row when `has_headers` is true and arrays otherwise. With a
+      # block each row is yielded instead of being collected into an array.
+      # CSV is loaded on first use since it is a bundled gem from Ruby 3.4,
+      # so applications on those versions need `csv` in their Gemfile.
+      def get_csv(uri, headers: {}, delimiter: ',', has_headers: true)
+        require 'csv'
+
+        response = get(uri, headers: { 'Accept' => 'text/csv' }.merge(headers))
+        csv = CSV.new(response.body, col_sep: delimiter, headers: has_headers)
+        rows = []
+        while (row = csv.shift)
+          row = row.to_h if has_headers
+          block_given? ? yield(row) : rows << row
+        end
+        rows
+      end
+
+      # Fetches a protobuf message and decodes it with `message_class`, such
+      # as a class generated by google-protobuf.
+      def get_protobuf(uri, message_class, headers: {})
+        response = get(uri, headers: { 'Accept' => 'application/x-protobuf' }.merge(headers))
+        message_class.decode(response.body)
+      end
+
+      # Fetches and parses a GeoJSON document served as application/geo+json
+      # or application/json.
+      def get_geojson(uri, headers: {})
+        response = get(uri, headers: { 'Accept' => GeoJson::CONTENT_TYPES.join(', ') }.merge(headers))
+        unless GeoJson::CONTENT_TYPES.include?(response.content_type)
+          raise UnexpectedContentTypeError.new(GeoJson::CONTENT_TYPES.join(' or '), response['Content-Type'])
+        end
+
+        GeoJson.new(JSON.parse(response.body))
+      end
+
+      # Sleeps for a random duration between 0 and `jitter_ms` milliseconds
+      # before issuing the GET to spread out requests from many clients.
+      def get_with_jitter(uri, headers: {}, body: {}, jitter_ms: 0, &block)
+        sleep(Random.rand(jitter_ms.to_f) / 1000) if jitter_ms.positive?
+        get(uri, headers: headers, body: body, &block)
+      end
+
+      # GETs every URL, at most `rps` a second, from up to `concurrency`
+      # threads. Returns a hash per URL, in the order given, holding either
+      # the response or the error message.
+      def throttled_get_all(urls, headers: {}, rps: 10, concurrency: 5)
+        fetcher = ThrottledFetcher.new(rps: rps, concurrency: concurrency) { client_with(@options) }
+        fetcher.get_all(urls, headers)
+      end
+
+      # Returns a FallbackResponse holding `fallback_body` instead of raising
+      # when the GET fails with a connection error or a 5xx response.
+      def get_with_fallback_body(uri, fallback_body, headers: {})
+        response = get(uri, headers: headers)
+        return response unless response.is_a?(Net::HTTPServerError)
+
+        FallbackResponse.new(fallback_body, "#{response.code} #{response.message}")
+      rescue *CONNECTION_ERRORS => error
+        FallbackResponse.new(fallback_body, error.message)
+      end
+
+      # Raises a StaleResourceError when the Last-Modified header of the
+      # response is more than `max_age_secs` seconds in the past.
+      def get_with_max_age_check(uri, max_age_secs:, headers: {})
+        response = get(uri, headers: headers)
+        raise MissingLastModifiedError, "#{uri} has no Last-Modified header" unless response['Last-Modified']
+
+        last_modified_at = Time.httpdate(response['Last-Modified'])
+        raise StaleResourceError.new(last_modified_at, max_age_secs) if Time.now - last_modified_at > max_age_secs
+
+        response
+      end
+
+      # Follows redirects with HEAD requests, recording each hop, then GETs
+      # the final location. Returns the final response as a
+      # RedirectedResponse, whose `redirect_history` lists each hop.
+      def get_with_redirect_history(uri, headers: {}, max_hops: 10)
+        history = []
+        url = uri.to_s
+        max_hops.times do
+          hop = redirect_hop_for(url, headers)
+          break if hop.nil?
+
+          history << hop
+          url = hop[:location]
+        end
+        response = execute(url, request_for(Net::HTTP::Get, url, headers: headers), limit: 0)
+        RedirectedResponse.new(response, history)
+      end
+
+      # Returns the body as a UTF-8 string with any byte order mark removed.
+      def get_text_encoding_normalised(uri, headers: {})
+        response = get(uri, headers: headers)
+        TextDecoder.new.decode(response.body, response.type_params['charset'])
+      end
+
+      private
+
+      def redirect_hop_for(url, headers)
+        connection = connection_for(url)
+        response = dispatch(connection, request_for(Net::HTTP::Head, url, headers: headers))
+        return unless follow_redirect?(url, connection, response)
+
+        location = connection.build_url_for(response['location'])
+        { url: url, status: response.code.to_i, location: location }
+      end
+    end
+  end
+end
@@ -0,0 +1,24 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Stands in for a response that could not be fetched, with a body given
+    # by the caller and the reason the request failed as its message.
+    class FallbackResponse
+      attr_reader :body, :message
+
+      def initialize(body, message)
+        @body = body
+        @message = message
+      end
+
+      def code
+        '0'
+      end
+
+      def fallback?
+        true
+      end
+    end
+  end
+end
@@ -0,0 +1,56 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # A parsed GeoJSON (RFC 7946) document.
+    class GeoJson
+      CONTENT_TYPES = %w[application/geo+json application/json].freeze
+
+      Feature = Struct.new(:geometry_type, :coordinates, :properties)
+
+      attr_reader :features
+
+      def initialize(document)
+        @document = document
+        @features = features_in(document).map do |feature|
+          geometry = feature['geometry'] || {}
+          Feature.new(geometry['type'], geometry['coordinates'], feature['properties'] || {})
+        end
+      end
+
+      def feature_count
+        features.size
+      end
+
+      # Returns the `bbox` member of the document, or [minx, miny, maxx, maxy]
+      # of every position in it, or nil when it has no positions.
+      def bounding_box
+        return @document['bbox'] if @document['bbox']
+
+        positions = features.flat_map { |x| positions_in(x.coordinates) }
+        return if positions.empty?
+
+        xs = positions.map(&:first)
+        ys = positions.map { |x| x[1] }
+        [xs.min, ys.min, xs.max, ys.max]
+      end
+
+      private
+
+      def features_in(document)
+        case document['type']
+        when 'FeatureCollection' then document.fetch('features', [])
+        when 'Feature' then [document]
+        else [{ 'geometry' => document }]
+        end
+      end
+
+      def positions_in(coordinates)
+        return [] unless coordinates.is_a?(Array)
+        return [coordinates] if coordinates.first.is_a?(Numeric)
+
+        coordinates.flat_map { |x| positions_in(x) }
+      end
+    end
+  end
+end
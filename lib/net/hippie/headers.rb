@@ -0,0 +1,71 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Builders for request header values. Net::Hippie extends this module so
+    # each is called as `Net::Hippie.name(...)`.
+    module Headers
+      COOKIE_NAME = /\A[!$#%&'*+\-.^_`|~0-9A-Za-z]+\z/.freeze
+      COOKIE_VALUE = /\A[\x21\x23-\x2B\x2D-\x3A\x3C-\x5B\x5D-\x7E]*\z/.freeze
+
+      TRACE_CONTEXT = {
+        trace_id: /\A\h{32}\z/,
+        parent_id: /\A\h{16}\z/,
+        flags: /\A\h{2}\z/
+      }.freeze
+
+      # Builds a Cookie header value. Names must be tokens and values must
+      # be RFC 6265 cookie-octets, so whitespace, `"`, `,`, `;`, `\` and
+      # non-ASCII characters raise an ArgumentError instead of being encoded.
+      def cookies(cookies)
+        cookies.map do |name, value|
+          unless COOKIE_NAME.match?(name.to_s) && COOKIE_VALUE.match?(value.to_s)
+            raise ArgumentError, "Invalid cookie: #{name}"
+          end
+
+          "#{name}=#{value}"
+        end.join('; ')
+      end
+
+      # Builds W3C Trace Context headers from a hash with `trace_id`,
+      # `parent_id`, `flags` and an optional `tracestate`.
+      def trace_context(span_context)
+        TRACE_CONTEXT.each do |key, pattern|
+          value = span_context[key]
+          raise ArgumentError, "Invalid #{key}: #{value.inspect}" unless pattern.match?(value.to_s)
+        end
+
+        values = span_context.values_at(*TRACE_CONTEXT.keys).map { |x| x.to_s.downcase }
+        headers = { 'traceparent' => "00-#{values.join('-')}" }
+        headers['tracestate'] = span_context[:tracestate] if span_context[:tracestate]
+        headers
+      end
+
+      # Builds the de-facto X-Forwarded-* headers and the RFC 7239 Forwarded
+      # header for a request to `uri` forwarded on behalf of `addresses`.
+      # Returns no headers when there are no addresses.
+      def forwarded_headers(addresses, uri)
+        addresses = Array(addresses)
+        return {} if addresses.empty?
+
+        uri = URI.parse(uri.to_s)
+        {
+          'X-Forwarded-For' => addresses.join(', '),
+          'X-Forwarded-Host' => uri.host,
+          'X-Forwarded-Proto' => uri.scheme,
+          'Forwarded' => forwarded(addresses, uri)
+        }
+      end
+
+      private
+
+      def forwarded(addresses, uri)
+        elements = addresses.map do |address|
+          address.to_s.include?(':') ? %(for="[#{address}]") : "for=#{address}"
+        end
+        elements[0] = "#{elements[0]};host=#{uri.host};proto=#{uri.scheme}"
+        elements.join(', ')
+      end
+    end
+  end
+end
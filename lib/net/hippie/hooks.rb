@@ -0,0 +1,97 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Client methods that register blocks to run around each request and
+    # log the outcome of each request.
+    module Hooks
+      # Registers a block called with each Net::HTTPRequest before it is
+      # sent. The block may add or change headers on the request.
+      def on_before_request(&block)
+        raise ArgumentError, 'no block given' unless block
+
+        hooks[:before_request] << block
+        self
+      end
+
+      # Registers a block called with each response and the milliseconds it
+      # took to arrive.
+      def on_after_response(&block)
+        raise ArgumentError, 'no block given' unless block
+
+        hooks[:after_response] << block
+        self
+      end
+
+      # Registers a block called with the error message and request when a
+      # request raises. The error is re-raised afterwards.
+      def on_error(&block)
+        raise ArgumentError, 'no block given' unless block
+
+        hooks[:error] << block
+        self
+      end
+
+      protected
+
+      def hooks
+        @hooks ||= Hash.new { |hash, key| hash[key] = [] }
+      end
+
+      private
+
+      # Builds a client with `options` that runs the same hooks as this one.
+      def client_with(options)
+        Client.new(options).tap do |client|
+          hooks.each { |name, blocks| client.hooks[name].concat(blocks) }
+        end
+      end
+
+      def dispatch(connection, request)
+        run_hooks(:before_request, request)
+        response, elapsed_ms = timed { connection.run(request) }
+        log_response(request, response, elapsed_ms)
+        run_hooks(:after_response, response, elapsed_ms)
+        response
+      rescue StandardError => error
+        log_error(request, error)
+        run_hooks(:error, error.message, request)
+        raise error
+      end
+
+      def run_hooks(kind, *args)
+        hooks[kind].each { |x| x.call(*args) }
+      end
+
+      def log_response(request, response, elapsed_ms)
+        log(:info, request, "#{response.code} #{elapsed_ms}ms")
+      end
+
+      def log_error(request, error)
+        log(:error, request, "#{error.class}: #{error.message}")
+      end
+
+      def log(level, request, message)
+        logger&.public_send(level, "#{request.method} #{loggable_url(request.uri)} #{message}")
+      end
+
+      # Query strings are left out of log lines unless the `log_query` option
+      # is set, in which case the `redact_query_params` are masked.
+      def loggable_url(uri)
+        url = uri.to_s
+        url = url.sub(/\?.*\z/m, '') unless @options[:log_query]
+        Redactor.from(@options).redact_url(url)
+      end
+
+      def timed
+        started_at = now
+        result = yield
+        [result, ((now - started_at) * 1000).round]
+      end
+
+      def now
+        Process.clock_gettime(Process::CLOCK_MONOTONIC)
+      end
+    end
+  end
+end
@@ -0,0 +1,35 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Decompresses a gzip or deflate body into an IO in fixed size chunks,
+    # so the uncompressed content is never held in memory at once.
+    class Inflater
+      CHUNK_SIZE = 64 * 1024
+      WINDOW_BITS = {
+        'gzip' => Zlib::MAX_WBITS + 16,
+        'deflate' => Zlib::MAX_WBITS
+      }.freeze
+
+      def initialize(encoding = 'gzip')
+        @window_bits = WINDOW_BITS.fetch(encoding.to_s.downcase) do
+          raise ArgumentError, "Unsupported encoding: #{encoding}"
+        end
+      end
+
+      # Writes the decompressed body to `io` and returns the number of
+      # bytes written.
+      def stream(body, io)
+        inflate = Zlib::Inflate.new(@window_bits)
+        source = StringIO.new(body.to_s)
+        written = 0
+        while (chunk = source.read(CHUNK_SIZE))
+          written += io.write(inflate.inflate(chunk))
+        end
+        written + io.write(inflate.finish)
+      ensure
+        inflate&.close
+      end
+    end
+  end
+end
@@ -0,0 +1,110 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Applies RFC 6902 JSON Patch operations to a parsed JSON document
+    # without modifying the original.
+    class JsonPatch
+      CONTENT_TYPE = 'application/json-patch+json'
+      OPERATIONS = %w[add remove replace move copy test].freeze
+
+      def initialize(operations)
+        @operations = operations.map { |x| x.map { |key, value| [key.to_s, value] }.to_h }
+      end
+
+      def apply(document)
+        @operations.inject(Marshal.load(Marshal.dump(document))) do |memo, operation|
+          op = operation['op'].to_s
+          raise JsonPatchError, "Unknown operation: #{op}" unless OPERATIONS.include?(op)
+
+          __send__("apply_#{op}", memo, operation)
+        end
+      end
+
+      private
+
+      def apply_add(document, operation)
+        add(document, pointer(operation['path']), operation.fetch('value'))
+      end
+
+      def apply_remove(document, operation)
+        remove(document, pointer(operation['path'])).first
+      end
+
+      def apply_replace(document, operation)
+        tokens = pointer(operation['path'])
+        return operation.fetch('value') if tokens.empty?
+
+        add(remove(document, tokens).first, tokens, operation.fetch('value'))
+      end
+
+      def apply_move(document, operation)
+        document, value = remove(document, pointer(operation['from']))
+        add(document, pointer(operation['path']), value)
+      end
+
+      def apply_copy(document, operation)
+        value = Marshal.load(Marshal.dump(get(document, pointer(operation['from']))))
+        add(document, pointer(operation['path']), value)
+      end
+
+      def apply_test(document, operation)
+        return document if get(document, pointer(operation['path'])) == operation['value']
+
+        raise JsonPatchError, "Test failed at #{operation['path']}"
+      end
+
+      def add(document, tokens, value)
+        return value if tokens.empty?
+
+        parent = get(document, tokens[0...-1])
+        if parent.is_a?(Array)
+          parent.insert(index_in(parent, tokens.last, parent.size), value)
+        else
+          container(parent)[tokens.last] = value
+        end
+        document
+      end
+
+      def remove(document, tokens)
+        raise JsonPatchError, 'Cannot remove the whole document' if tokens.empty?
+
+        parent = get(document, tokens[0...-1])
+        return [document, parent.delete_at(index_in(parent, tokens.last, parent.size - 1))] if parent.is_a?(Array)
+        raise JsonPatchError, "No member #{tokens.last}" unless container(parent).key?(tokens.last)
+
+        [document, parent.delete(tokens.last)]
+      end
+
+      def get(document, tokens)
+        tokens.inject(document) do |memo, token|
+          next memo[index_in(memo, token, memo.size - 1)] if memo.is_a?(Array)
+          raise JsonPatchError, "No member #{token}" unless container(memo).key?(token)
+
+          memo[token]
+        end
+      end
+
+      def index_in(array, token, max)
+        return array.size if token == '-' && max == array.size
+        raise JsonPatchError, "Invalid index #{token}" unless token.match?(/\A(0|[1-9]\d*)\z/) && token.to_i <= max
+
+        token.to_i
+      end
+
+      def container(value)
+        raise JsonPatchError, 'Path does not refer to an object or array' unless value.is_a?(Hash)
+
+        value
+      end
+
+      def pointer(path)
+        raise JsonPatchError, "Invalid path: #{path.inspect}" unless path.is_a?(String)
+        return [] if path.empty?
+        raise JsonPatchError, "Invalid path: #{path}" unless path.start_with?('/')
+
+        path.split('/', -1).drop(1).map { |x| x.gsub('~1', '/').gsub('~0', '~') }
+      end
+    end
+  end
+end
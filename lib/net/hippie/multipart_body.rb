@@ -0,0 +1,93 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Streams a multipart/form-data body. Parts follow the format of
+    # Net::HTTP#set_form and values that respond to `read` are read as the
+    # body is sent instead of being loaded into memory. Seekable values are
+    # rewound first, so each body sends them from the start.
+    class MultipartBody
+      CONTENT_TYPES = {
+        '.csv' => 'text/csv',
+        '.gif' => 'image/gif',
+        '.gz' => 'application/gzip',
+        '.html' => 'text/html',
+        '.jpeg' => 'image/jpeg',
+        '.jpg' => 'image/jpeg',
+        '.json' => 'application/json',
+        '.pdf' => 'application/pdf',
+        '.png' => 'image/png',
+        '.txt' => 'text/plain',
+        '.xml' => 'application/xml',
+        '.zip' => 'application/zip'
+      }.freeze
+
+      attr_reader :boundary
+
+      def self.content_type_for(path)
+        CONTENT_TYPES.fetch(File.extname(path.to_s).downcase, 'application/octet-stream')
+      end
+
+      def initialize(parts, boundary: SecureRandom.hex(16))
+        @boundary = boundary
+        @segments = parts.flat_map { |name, value, options| segments_for(name, value, options || {}) }
+        @segments << StringIO.new("--#{boundary}--\r\n")
+        rewind
+      end
+
+      def content_type
+        "multipart/form-data; boundary=#{boundary}"
+      end
+
+      def size
+        sizes = @segments.map { |x| x.size if x.respond_to?(:size) }
+        sizes.sum unless sizes.include?(nil)
+      end
+
+      def read(length = nil, outbuf = nil)
+        data = String.new(encoding: Encoding::BINARY)
+        read_segment(data, length) until eof? || (length && data.bytesize >= length)
+        return if length && data.empty?
+
+        outbuf ? outbuf.replace(data) : data
+      end
+
+      # Rewinds every part so the body can be sent again from the start.
+      def rewind
+        @segments.each { |x| rewind_segment(x) }
+        @index = 0
+      end
+
+      private
+
+      def eof?
+        @index == @segments.size
+      end
+
+      def read_segment(data, length)
+        chunk = @segments[@index].read(length && length - data.bytesize)
+        if chunk.nil? || chunk.empty?
+          @index += 1
+        else
+          data << chunk.b
+        end
+      end
+
+      def rewind_segment(segment)
+        segment.rewind if segment.respond_to?(:rewind)
+      rescue Errno::ESPIPE
+        nil
+      end
+
+      def segments_for(name, value, options)
+        disposition = %(Content-Disposition: form-data; name="#{name}")
+        return [StringIO.new("--#{boundary}\r\n#{disposition}\r\n\r\n#{value}\r\n")] unless value.respond_to?(:read)
+
+        filename = options.fetch(:filename) { File.basename(value.respond_to?(:path) ? value.path : name.to_s) }
+        content_type = options.fetch(:content_type, 'application/octet-stream')
+        head = "--#{boundary}\r\n#{disposition}; filename=\"#{filename}\"\r\nContent-Type: #{content_type}\r\n\r\n"
+        [StringIO.new(head), value, StringIO.new("\r\n")]
+      end
+    end
+  end
+end
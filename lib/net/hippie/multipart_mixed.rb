@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Splits a multipart/mixed batch response into the HTTP responses
+    # embedded in each of its parts.
+    class MultipartMixed
+      STATUS_LINE = %r{\AHTTP/\d(?:\.\d)? (\d{3})(?: ([^\r\n]*))?\z}.freeze
+
+      # An HTTP response embedded in a part. Header names are lowercase.
+      Part = Struct.new(:code, :message, :headers, :body) do
+        def [](name)
+          headers[name.to_s.downcase]
+        end
+
+        def content_type
+          self['content-type']&.split(';')&.first&.strip
+        end
+      end
+
+      def initialize(response)
+        @response = response
+      end
+
+      # Parts that cannot be parsed are returned with a code of '0' and the
+      # raw part as the body.
+      def responses
+        parts.map { |part| parse(part) }
+      end
+
+      private
+
+      def parts
+        boundary = @response.type_params['boundary']
+        return [] if boundary.nil?
+
+        sections = @response.body.to_s.split(/\r?\n?--#{Regexp.escape(boundary)}/)
+        sections.drop(1).take_while { |x| !x.start_with?('--') }.map { |x| x.sub(/\A[ \t]*\r?\n/, '') }
+      end
+
+      def parse(part)
+        _, message = part.split(/\r?\n\r?\n/, 2)
+        head, body = message.to_s.split(/\r?\n\r?\n/, 2)
+        status_line, *lines = head.to_s.split(/\r?\n/)
+        status = STATUS_LINE.match(status_line.to_s)
+        return Part.new('0', 'Unparseable part', {}, part) if status.nil?
+
+        headers = headers_from(lines)
+        Part.new(status[1], status[2].to_s, headers, truncate(body.to_s, headers['content-length']))
+      end
+
+      def truncate(body, content_length)
+        content_length.to_s.match?(/\A\d+\z/) ? body.byteslice(0, content_length.to_i) : body
+      end
+
+      def headers_from(lines)
+        lines.each_with_object({}) do |line, headers|
+          name, value = line.split(':', 2)
+          key = name.strip.downcase
+          headers[key] = [headers[key], value.to_s.strip].compact.join(', ')
+        end
+      end
+    end
+  end
+end
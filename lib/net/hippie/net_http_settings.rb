@@ -0,0 +1,49 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Reads the settings of an existing Net::HTTP instance as Client options.
+    class NetHttpSettings
+      UNSUPPORTED = %i[ca_path ssl_version ciphers proxy_user].freeze
+
+      def initialize(http)
+        @http = http
+      end
+
+      def to_h
+        timeouts.merge(tls).merge(proxy).compact
+      end
+
+      def warn_unsupported(logger)
+        UNSUPPORTED.each do |name|
+          logger.warn("Ignoring unsupported Net::HTTP setting `#{name}`") if @http.public_send(name)
+        end
+      end
+
+      private
+
+      def timeouts
+        settings = { open_timeout: @http.open_timeout, read_timeout: @http.read_timeout }
+        settings[:write_timeout] = @http.write_timeout if @http.respond_to?(:write_timeout)
+        settings
+      end
+
+      def tls
+        settings = {
+          verify_mode: @http.verify_mode,
+          ca_file: @http.ca_file,
+          certificate: @http.cert&.to_pem,
+          key: @http.key&.to_pem
+        }
+        settings[:verify_hostname] = @http.verify_hostname if @http.respond_to?(:verify_hostname)
+        settings
+      end
+
+      def proxy
+        return {} unless @http.proxy?
+
+        { proxy_addr: @http.proxy_address, proxy_port: @http.proxy_port }
+      end
+    end
+  end
+end
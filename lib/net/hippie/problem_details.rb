@@ -0,0 +1,31 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # An RFC 7807 problem document describing why a request failed.
+    class ProblemDetails
+      CONTENT_TYPE = 'application/problem+json'
+      MEMBERS = %w[type title status detail instance].freeze
+
+      attr_reader :type, :title, :status, :detail, :instance, :extensions
+
+      def self.from(response)
+        return unless response.content_type == CONTENT_TYPE
+
+        document = JSON.parse(response.body)
+        new(document) if document.is_a?(Hash)
+      rescue JSON::ParserError
+        nil
+      end
+
+      def initialize(document)
+        @type = document.fetch('type', 'about:blank')
+        @title = document['title']
+        @status = document['status']
+        @detail = document['detail']
+        @instance = document['instance']
+        @extensions = document.reject { |key, _| MEMBERS.include?(key) }
+      end
+    end
+  end
+end
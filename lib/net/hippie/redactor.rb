@@ -0,0 +1,76 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Masks sensitive header values and query parameters before they are
+    # written anywhere outside of the request itself.
+    class Redactor
+      HEADERS = %w[
+        Authorization
+        Proxy-Authorization
+        Cookie
+        Set-Cookie
+        X-Api-Key
+      ].freeze
+      REDACTED = '[REDACTED]'
+
+      # Redacts messages before handing them to an output that receives
+      # them via `<<`, such as the Logger given to Net::HTTP#set_debug_output.
+      Output = Struct.new(:redactor, :output) do
+        def <<(message)
+          output << redactor.redact(message)
+        end
+      end
+
+      def self.from(options)
+        new(
+          headers: options.fetch(:redact_headers, HEADERS),
+          query_params: options.fetch(:redact_query_params, [])
+        )
+      end
+
+      def initialize(headers: HEADERS, query_params: [])
+        @headers = headers.map { |x| x.to_s.downcase }
+        @query_params = query_params.map(&:to_s)
+      end
+
+      def redact_headers(headers)
+        headers.map do |key, value|
+          [key, @headers.include?(key.to_s.downcase) ? REDACTED : value]
+        end.to_h
+      end
+
+      def redact_url(url)
+        return url.to_s if @query_params.empty?
+
+        url.to_s.gsub(query_pattern) { "#{Regexp.last_match(1)}#{REDACTED}" }
+      end
+
+      def redact(text)
+        text = text.to_s
+        text = text.gsub(header_pattern) { "#{Regexp.last_match(1)}: #{REDACTED}" } unless @headers.empty?
+        redact_url(text)
+      end
+
+      def wrap(output)
+        Output.new(self, output)
+      end
+
+      private
+
+      def header_pattern
+        @header_pattern ||= begin
+          names = @headers.map { |x| Regexp.escape(x) }.join('|')
+          /\b(#{names}):[^\r\n]*?(?=\\r\\n|\r?\n|\z)/i
+        end
+      end
+
+      def query_pattern
+        @query_pattern ||= begin
+          names = @query_params.map { |x| Regexp.escape(x) }.join('|')
+          /([?&](?:#{names})=)[^&#\s"\\]*/
+        end
+      end
+    end
+  end
+end
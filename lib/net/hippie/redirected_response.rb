@@ -0,0 +1,16 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # A response that also lists the redirects followed to reach it, as
+    # hashes with the `url`, `status` and `location` of each hop.
+    class RedirectedResponse < SimpleDelegator
+      attr_reader :redirect_history
+
+      def initialize(response, redirect_history)
+        super(response)
+        @redirect_history = redirect_history
+      end
+    end
+  end
+end
@@ -0,0 +1,136 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Helpers that read or check a Net::HTTPResponse. Net::Hippie extends
+    # this module so each is called as `Net::Hippie.name(response)`.
+    module Responses
+      MEDIA_TYPES = {
+        json: %r{\A(application/json|text/json|application/[^/]+\+json)},
+        xml: %r{\A(application/xml|text/xml|application/[^/]+\+xml)},
+        html: %r{\A(text/html|application/xhtml\+xml)},
+        binary: %r{\A(image/|audio/|video/|application/octet-stream)},
+        text: %r{\Atext/}
+      }.freeze
+
+      STRUCTURED_FIELDS = {
+        dictionary: %w[priority cdn-cache-control content-digest repr-digest signature signature-input],
+        list: %w[accept-ch cache-status proxy-status],
+        item: %w[cross-origin-embedder-policy cross-origin-opener-policy origin-agent-cluster]
+      }.freeze
+
+      # Checks the body of a response against each digest in its
+      # Content-Digest header and raises an IntegrityError on a mismatch.
+      def verify_content_digest(response)
+        ContentDigest.new(ContentDigest::ALGORITHMS.keys).verify(response)
+      end
+
+      # Returns the HTTP responses embedded in a multipart/mixed batch response
+      # as MultipartMixed::Part values with a code, message, headers and body.
+      def parse_multipart_mixed(response)
+        MultipartMixed.new(response).responses
+      end
+
+      # Returns a hash with the `scheme` and `params` of each challenge in the
+      # WWW-Authenticate headers of a response.
+      def parse_www_authenticate(response)
+        Authenticator.new.challenges_in(response).map do |scheme, params|
+          { scheme: scheme, params: params }
+        end
+      end
+
+      def json?(response)
+        media_type?(response, :json)
+      end
+
+      def xml?(response)
+        media_type?(response, :xml)
+      end
+
+      def html?(response)
+        media_type?(response, :html)
+      end
+
+      # Returns true for image, audio, video and application/octet-stream
+      # responses.
+      def binary?(response)
+        media_type?(response, :binary)
+      end
+
+      def text?(response)
+        media_type?(response, :text)
+      end
+
+      def base64_body(response)
+        ::Base64.strict_encode64(response.body.to_s)
+      end
+
+      # Returns the body as unpadded URL-safe Base64.
+      def base64_body_url_safe(response)
+        ::Base64.urlsafe_encode64(response.body.to_s, padding: false)
+      end
+
+      def hex_body(response)
+        response.body.to_s.unpack1('H*')
+      end
+
+      # Decompresses a gzip or deflate body into `io` in chunks and returns
+      # the number of bytes written.
+      def decompress_and_stream(response, io, encoding: 'gzip')
+        Inflater.new(encoding).stream(response.body, io)
+      end
+
+      # Decompresses a gzip body into the file at `dest_path` in chunks and
+      # returns the number of bytes written.
+      def gzip_body_stream(response, dest_path)
+        File.open(dest_path, 'wb') { |file| decompress_and_stream(response, file) }
+      end
+
+      # Parses an RFC 8941 structured header using the type of a well known
+      # field, or else the first of dictionary, item and list that fits.
+      # Returns nil when the header is missing.
+      def structured_header(response, name)
+        value = response[name]
+        return if value.nil?
+
+        type, = STRUCTURED_FIELDS.find { |_, names| names.include?(name.to_s.downcase) }
+        parse_structured(value, type ? [type] : %i[dictionary item list])
+      end
+
+      # Returns true for the FallbackResponse of `Client#get_with_fallback_body`.
+      def fallback?(response)
+        response.is_a?(FallbackResponse)
+      end
+
+      # Returns the status line and each header on its own line, followed by
+      # the first 200 bytes of the body.
+      def pretty_print_headers(response)
+        lines = ["HTTP/#{response.http_version} #{response.code} #{response.message}"]
+        response.each_capitalized { |name, value| lines << "#{name}: #{value}" }
+        body = response.body.to_s
+        lines << '' << body.byteslice(0, 200) unless body.empty?
+        lines.join("\n")
+      end
+
+      # Returns a one line summary of the status, content type and body size.
+      def inspect_response(response)
+        "#<#{response.class} #{response.code} #{response.message} " \
+          "#{response.content_type || '-'} #{response.body.to_s.bytesize} bytes>"
+      end
+
+      private
+
+      def parse_structured(value, types)
+        StructuredFields.parse(value, type: types.first)
+      rescue ArgumentError
+        raise if types.size == 1
+
+        parse_structured(value, types.drop(1))
+      end
+
+      def media_type?(response, name)
+        MEDIA_TYPES.fetch(name).match?(response.content_type.to_s.downcase)
+      end
+    end
+  end
+end
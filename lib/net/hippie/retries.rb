@@ -0,0 +1,84 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Client methods that retry requests after connection errors and
+    # responses asking the client to try again.
+    module Retries
+      # attempt 1 -> delay 0.1 second
+      # attempt 2 -> delay 0.2 second
+      # attempt 3 -> delay 0.4 second
+      # attempt 4 -> delay 0.8 second
+      # attempt 5 -> delay 1.6 second
+      # attempt 6 -> delay 3.2 second
+      # attempt 7 -> delay 6.4 second
+      # attempt 8 -> delay 12.8 second
+      #
+      # When the `max_retry_after_secs` option is set, a 429 or 503 with a
+      # Retry-After header is retried after the requested delay instead and
+      # a RateLimitedError is raised when that delay is longer.
+      def with_retry(retries: 3)
+        retries = 0 if retries.nil? || retries.negative?
+
+        0.upto(retries) do |n|
+          attempt(n, retries) do
+            response = yield self
+            return response if n == retries || !wait_for_retry_after(response)
+          end
+        end
+      end
+
+      # Retries a GET on connection errors and on the `retry_statuses` option
+      # (502, 503 and 504 by default), doubling `base_delay_ms` after each
+      # attempt. With `jitter` each delay varies by up to 10%.
+      def get_with_exponential_retry(uri, headers: {}, base_delay_ms: 100, max_attempts: 5, jitter: true)
+        delay = base_delay_ms / 1000.0
+        1.upto([max_attempts, 1].max) do |n|
+          last = n >= max_attempts
+          response = retryable_get(uri, headers, last)
+          return response if last || !retry_status?(response)
+
+          sleep(jitter ? delay * Random.rand(0.9..1.1) : delay)
+          delay *= 2
+        end
+      end
+
+      private
+
+      def attempt(attempt, max)
+        yield
+      rescue *CONNECTION_ERRORS => error
+        raise error if attempt == max
+
+        delay = ((2**attempt) * 0.1) + Random.rand(0.05) # delay + jitter
+        logger.warn("`#{error.message}` #{attempt + 1}/#{max} Delay: #{delay}s")
+        sleep delay
+      end
+
+      def retryable_get(uri, headers, last)
+        get(uri, headers: headers)
+      rescue *CONNECTION_ERRORS => error
+        raise error if last
+
+        logger.warn("`#{error.message}` retrying #{loggable_url(uri)}")
+        nil
+      end
+
+      def retry_status?(response)
+        response.nil? || @options.fetch(:retry_statuses, [502, 503, 504]).include?(response.code.to_i)
+      end
+
+      def wait_for_retry_after(response)
+        max = @options[:max_retry_after_secs]
+        delay = RetryAfter.delay_for(response) unless max.nil?
+        return false if delay.nil?
+
+        raise RateLimitedError, "Retry-After of #{delay}s exceeds #{max}s" if delay > max
+
+        logger.warn("`#{response.code}` Retry-After: #{delay}s")
+        sleep delay
+        true
+      end
+    end
+  end
+end
@@ -0,0 +1,21 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Reads the delay requested by the Retry-After header of a 429 or 503.
+    class RetryAfter
+      RESPONSES = [Net::HTTPTooManyRequests, Net::HTTPServiceUnavailable].freeze
+
+      def self.delay_for(response, now = Time.now)
+        return unless RESPONSES.any? { |x| response.is_a?(x) }
+
+        value = response['Retry-After'].to_s.strip
+        return value.to_i if value.match?(/\A\d+\z/)
+
+        [Time.httpdate(value) - now, 0].max
+      rescue ArgumentError
+        nil
+      end
+    end
+  end
+end
@@ -0,0 +1,38 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Summarises what a server supports from the headers of a response.
+    class ServerCapabilities
+      def initialize(response)
+        @response = response
+      end
+
+      def to_h
+        {
+          server: @response['Server'],
+          powered_by: @response['X-Powered-By'],
+          http_version: @response.http_version,
+          compression: list_of('Content-Encoding'),
+          auth_schemes: Authenticator.new.challenges_in(@response).map(&:first),
+          cors_enabled: @response.key?('Access-Control-Allow-Origin'),
+          hsts_enabled: @response.key?('Strict-Transport-Security'),
+          content_types_supported: content_types
+        }
+      end
+
+      private
+
+      def content_types
+        [@response.content_type, *list_of('Accept-Post'), *list_of('Accept-Patch')].compact.uniq
+      end
+
+      def list_of(name)
+        @response.get_fields(name).to_a
+          .flat_map { |x| x.split(',') }
+          .map(&:strip)
+          .reject(&:empty?)
+      end
+    end
+  end
+end
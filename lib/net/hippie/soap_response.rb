@@ -0,0 +1,23 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # A response to a SOAP request that can report the Fault element of a
+    # SOAP 1.1 or 1.2 envelope.
+    class SoapResponse < SimpleDelegator
+      FAULT = /<(?:[\w-]+:)?Fault[\s>]/.freeze
+      FAULT_STRING = %r{<(?:[\w-]+:)?faultstring[^>]*>(.*?)</(?:[\w-]+:)?faultstring>}m.freeze
+      REASON_TEXT = %r{<(?:[\w-]+:)?Reason[^>]*>\s*<(?:[\w-]+:)?Text[^>]*>(.*?)</(?:[\w-]+:)?Text>}m.freeze
+
+      def fault?
+        FAULT.match?(body.to_s)
+      end
+
+      def fault_message
+        return unless fault?
+
+        body.to_s[FAULT_STRING, 1] || body.to_s[REASON_TEXT, 1]
+      end
+    end
+  end
+end
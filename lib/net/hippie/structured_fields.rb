@@ -0,0 +1,129 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Parses RFC 8941 Structured Field Values. Integers, decimals, strings
+    # and booleans become their Ruby equivalents, tokens become symbols and
+    # byte sequences become binary strings. Raises an ArgumentError when the
+    # value is not a valid structured field of the requested type.
+    class StructuredFields
+      Item = Struct.new(:value, :params)
+      InnerList = Struct.new(:items, :params)
+
+      KEY = /[a-z*][a-z0-9_\-.*]*/.freeze
+      TOKEN = %r{[A-Za-z*][!$#%&'*+\-.^_`|~0-9A-Za-z:/]*}.freeze
+      STRING = /((?:[\x20\x21\x23-\x5B\x5D-\x7E]|\\["\\])*)"/.freeze
+      BYTES = %r{:([A-Za-z0-9+/=]*):}.freeze
+      NUMBER = /-?\d+(?:\.\d+)?/.freeze
+      TYPES = { item: :parse_item, list: :parse_list, dictionary: :parse_dictionary }.freeze
+
+      def self.parse(value, type: :item)
+        new(value).parse(type)
+      end
+
+      def initialize(value)
+        @scanner = StringScanner.new(value.to_s.strip)
+      end
+
+      def parse(type)
+        parser = TYPES.fetch(type) { raise ArgumentError, "Unknown structured field type: #{type}" }
+        result = send(parser)
+        invalid! unless @scanner.eos?
+        result
+      end
+
+      private
+
+      def parse_list
+        members = []
+        until @scanner.eos?
+          members << parse_item_or_inner_list
+          break unless next_member?
+        end
+        members
+      end
+
+      def parse_dictionary
+        members = {}
+        until @scanner.eos?
+          key = parse_key
+          members[key] = @scanner.skip(/=/) ? parse_item_or_inner_list : Item.new(true, parse_params)
+          break unless next_member?
+        end
+        members
+      end
+
+      def next_member?
+        @scanner.skip(/[ \t]*/)
+        return false if @scanner.eos?
+
+        invalid! unless @scanner.skip(/,[ \t]*/) && !@scanner.eos?
+        true
+      end
+
+      def parse_item_or_inner_list
+        @scanner.skip(/\(/) ? parse_inner_list : parse_item
+      end
+
+      def parse_inner_list
+        items = []
+        loop do
+          @scanner.skip(/ */)
+          break if @scanner.skip(/\)/)
+
+          items << parse_item
+          invalid! unless @scanner.check(/[ )]/)
+        end
+        InnerList.new(items, parse_params)
+      end
+
+      def parse_item
+        Item.new(parse_bare_item, parse_params)
+      end
+
+      def parse_params
+        params = {}
+        while @scanner.skip(/;/)
+          @scanner.skip(/ */)
+          key = parse_key
+          params[key] = @scanner.skip(/=/) ? parse_bare_item : true
+        end
+        params
+      end
+
+      def parse_key
+        @scanner.scan(KEY) || invalid!
+      end
+
+      def parse_bare_item
+        if @scanner.check(NUMBER) then parse_number
+        elsif @scanner.skip(/"/) then parse_string
+        elsif @scanner.scan(TOKEN) then @scanner.matched.to_sym
+        elsif @scanner.scan(BYTES) then ::Base64.strict_decode64(@scanner[1])
+        elsif @scanner.scan(/\?[01]/) then @scanner.matched == '?1'
+        else invalid!
+        end
+      rescue ArgumentError
+        invalid!
+      end
+
+      def parse_number
+        number = @scanner.scan(NUMBER)
+        integer, fraction = number.delete('-').split('.')
+        return Integer(number, 10) if fraction.nil? && integer.length <= 15
+        return Float(number) if fraction && integer.length <= 12 && fraction.length <= 3
+
+        invalid!
+      end
+
+      def parse_string
+        invalid! unless @scanner.scan(STRING)
+        @scanner[1].gsub(/\\(["\\])/, '\1')
+      end
+
+      def invalid!
+        raise ArgumentError, "Invalid structured field at position #{@scanner.pos}"
+      end
+    end
+  end
+end
@@ -0,0 +1,33 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Converts a response body to UTF-8, honouring and removing any
+    # byte order mark. Invalid byte sequences are replaced with U+FFFD.
+    class TextDecoder
+      BOMS = {
+        "\xEF\xBB\xBF".b => Encoding::UTF_8,
+        "\xFF\xFE".b => Encoding::UTF_16LE,
+        "\xFE\xFF".b => Encoding::UTF_16BE
+      }.freeze
+
+      def decode(body, charset = nil)
+        bytes = body.to_s.b
+        bom, encoding = BOMS.find { |x, _| bytes.start_with?(x) }
+        encoding ||= find_encoding(charset)
+        text = bytes.byteslice(bom.to_s.bytesize, bytes.bytesize).force_encoding(encoding)
+        return text.scrub if encoding == Encoding::UTF_8
+
+        text.encode(Encoding::UTF_8, invalid: :replace, undef: :replace)
+      end
+
+      private
+
+      def find_encoding(charset)
+        charset ? Encoding.find(charset) : Encoding::UTF_8
+      rescue ArgumentError
+        Encoding::UTF_8
+      end
+    end
+  end
+end
@@ -0,0 +1,29 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Spaces out calls to `wait`, across threads, to at most `rps` a second.
+    class Throttle
+      def initialize(rps)
+        @interval = 1.0 / rps
+        @mutex = Mutex.new
+        @next_at = now
+      end
+
+      def wait
+        delay = @mutex.synchronize do
+          at = [@next_at, now].max
+          @next_at = at + @interval
+          at - now
+        end
+        sleep(delay) if delay.positive?
+      end
+
+      private
+
+      def now
+        Process.clock_gettime(Process::CLOCK_MONOTONIC)
+      end
+    end
+  end
+end
@@ -0,0 +1,48 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # GETs a list of URLs from a pool of threads, each with its own client
+    # built by the given block, at a rate limited by a Throttle.
+    class ThrottledFetcher
+      def initialize(rps:, concurrency:, &client_factory)
+        @throttle = Throttle.new(rps)
+        @concurrency = concurrency
+        @client_factory = client_factory
+      end
+
+      def get_all(urls, headers)
+        queue = work_queue_for(urls)
+        results = Array.new(urls.size)
+        Array.new([@concurrency, urls.size].min) do
+          Thread.new(@client_factory.call) do |client|
+            drain(queue) { |url, index| results[index] = get(client, url, headers) }
+          end
+        end.each(&:join)
+        results
+      end
+
+      private
+
+      def work_queue_for(items)
+        Queue.new.tap do |queue|
+          items.each_with_index { |item, index| queue << [item, index] }
+          queue.close
+        end
+      end
+
+      def drain(queue)
+        while (item = queue.pop)
+          yield item
+        end
+      end
+
+      def get(client, url, headers)
+        @throttle.wait
+        { url: url, response: client.get(url, headers: headers), error: nil }
+      rescue StandardError => error
+        { url: url, response: nil, error: error.message }
+      end
+    end
+  end
+end
@@ -0,0 +1,123 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Client methods that send files, streams and other specialised bodies.
+    module Uploads
+      BEACON_TIMEOUT = 5
+      SOAP_12_ENVELOPE = 'http://www.w3.org/2003/05/soap-envelope'
+
+      # POSTs a multipart/form-data body built from `parts`, given in the
+      # format of Net::HTTP#set_form. IO values are streamed as they are sent.
+      def post_multipart_streaming(uri, parts, headers: {})
+        multipart = MultipartBody.new(parts)
+        headers = { 'Content-Type' => multipart.content_type }.merge(headers)
+        request = request_for(Net::HTTP::Post, uri, headers: headers) { |x| attach_body_stream(x, multipart) }
+        execute(uri, request)
+      end
+
+      # POSTs `fields` followed by the file at `file_path`, as used by S3
+      # presigned POST. The file part uses the `Content-Type` field when
+      # given and otherwise a type guessed from the file extension.
+      def post_form_with_file(uri, fields, file_field_name, file_path, headers: {})
+        content_type = fields.fetch('Content-Type') { MultipartBody.content_type_for(file_path) }
+        File.open(file_path, 'rb') do |file|
+          options = { filename: File.basename(file_path), content_type: content_type }
+          post_multipart_streaming(uri, fields.to_a << [file_field_name, file, options], headers: headers)
+        end
+      end
+
+      # POSTs `data` as text/plain from a background thread and returns true
+      # without waiting for the response. The connection gives up when it
+      # waits more than BEACON_TIMEOUT seconds and errors are ignored.
+      def send_beacon(uri, data)
+        timeouts = { open_timeout: BEACON_TIMEOUT, read_timeout: BEACON_TIMEOUT, write_timeout: BEACON_TIMEOUT }
+        client = client_with(@options.merge(timeouts))
+        Thread.new { deliver_beacon(client, uri, data) }
+        true
+      end
+
+      # POSTs and raises a ProblemDetailsError when a 4xx or 5xx response
+      # carries an RFC 7807 problem document.
+      def post_expecting(uri, headers: {}, body: {})
+        raise_problem_details(post(uri, headers: headers, body: body))
+      end
+
+      # Uploads a file with the fields of an S3 presigned POST, sending the
+      # file last as S3 requires. Returns whether the upload succeeded with
+      # either the location of the object or the error S3 reported.
+      def post_multipart_presigned_s3(fields, file_path, endpoint_url)
+        response = post_form_with_file(endpoint_url, fields, 'file', file_path, headers: { 'Accept' => '*/*' })
+        body = response.body.to_s
+        if response.is_a?(Net::HTTPSuccess)
+          { success: true, error: nil, location: response['Location'] || xml_text(body, 'Location') }
+        else
+          error = [xml_text(body, 'Code'), xml_text(body, 'Message')].compact.join(': ')
+          { success: false, error: error.empty? ? "#{response.code} #{response.message}" : error, location: nil }
+        end
+      end
+
+      # Sends RFC 6902 JSON Patch `operations` with PATCH and raises a
+      # ProblemDetailsError when the server rejects them with a problem
+      # document.
+      def post_application_json_patch(uri, operations, headers: {})
+        headers = { 'Content-Type' => JsonPatch::CONTENT_TYPE }.merge(headers)
+        raise_problem_details(patch(uri, headers: headers, body: JSON.generate(operations)))
+      end
+
+      # Streams a POST body using chunked transfer encoding. The block is
+      # called for each chunk and returns nil once there is nothing left.
+      def post_chunked(uri, headers: {}, chunk_size: 8192, &block)
+        headers = { 'Transfer-Encoding' => 'chunked' }.merge(headers)
+        request = request_for(Net::HTTP::Post, uri, headers: headers) do |x|
+          x.body_stream = ChunkedBody.new(chunk_size, &block)
+        end
+        execute(uri, request)
+      end
+
+      # POSTs a SOAP envelope with the `SOAPAction` header. Envelopes in the
+      # SOAP 1.2 namespace are sent as application/soap+xml and others as
+      # SOAP 1.1 text/xml.
+      def post_soap(uri, soap_action, xml_body, headers: {})
+        content_type = if xml_body.include?(SOAP_12_ENVELOPE)
+                         %(application/soap+xml; charset=utf-8; action="#{soap_action}")
+                       else
+                         'text/xml; charset=utf-8'
+                       end
+        headers = { 'Content-Type' => content_type, 'SOAPAction' => %("#{soap_action}") }.merge(headers)
+        SoapResponse.new(post(uri, headers: { 'Accept' => '*/*' }.merge(headers), body: xml_body))
+      end
+
+      private
+
+      def attach_body_stream(request, body)
+        if body.size
+          request.content_length = body.size
+        else
+          request['Transfer-Encoding'] = 'chunked'
+        end
+        request.body_stream = body
+      end
+
+      def deliver_beacon(client, uri, data)
+        headers = { 'Content-Type' => 'text/plain', 'User-Agent' => 'net-hippie-beacon' }
+        client.post(uri, headers: headers, body: data)
+      rescue StandardError
+        nil
+      end
+
+      def xml_text(xml, element)
+        xml[%r{<#{element}>(.*?)</#{element}>}m, 1]
+      end
+
+      def raise_problem_details(response)
+        return response unless response.code.start_with?('4', '5')
+
+        problem = ProblemDetails.from(response)
+        raise ProblemDetailsError, problem if problem
+
+        response
+      end
+    end
+  end
+end
@@ -0,0 +1,40 @@
+# frozen_string_literal: true
+
+module Net
+  module Hippie
+    # Builds RFC 8292 VAPID Authorization headers for Web Push requests.
+    class VAPIDBuilder
+      DEFAULT_EXPIRATION = 12 * 60 * 60
+
+      def initialize(subject:, public_key_pem:, private_key_pem:)
+        @subject = subject
+        @public_key = OpenSSL::PKey::EC.new(public_key_pem)
+        @private_key = OpenSSL::PKey::EC.new(private_key_pem)
+      end
+
+      def build_authorization_header(audience:, expiration: nil)
+        expiration ||= Time.now.to_i + DEFAULT_EXPIRATION
+        claims = { aud: audience, exp: expiration.to_i, sub: @subject }
+        "vapid t=#{jwt_for(claims)}, k=#{encode(@public_key.public_key.to_bn.to_s(2))}"
+      end
+
+      private
+
+      def jwt_for(claims)
+        data = [{ typ: 'JWT', alg: 'ES256' }, claims].map { |x| encode(JSON.generate(x)) }.join('.')
+        "#{data}.#{encode(signature_for(data))}"
+      end
+
+      # JWS wants the raw r and s values rather than the DER encoded
+      # signature that OpenSSL produces.
+      def signature_for(data)
+        der = @private_key.sign(OpenSSL::Digest.new('SHA256'), data)
+        OpenSSL::ASN1.decode(der).value.map { |x| x.value.to_s(2).rjust(32, "\0") }.join
+      end
+
+      def encode(value)
+        ::Base64.urlsafe_encode64(value, padding: false)
+      end
+    end
+  end
+end
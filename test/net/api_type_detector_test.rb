@@ -0,0 +1,53 @@
+require 'test_helper'
+
+class ApiTypeDetectorTest < Minitest::Test
+  attr_reader :subject
+
+  def initialize(*args)
+    super
+    @subject = Net::Hippie::ApiTypeDetector.new
+  end
+
+  def test_detects_graphql_from_path
+    assert_equal :graphql, subject.detect('https://www.example.org/graphql', response_with('{}', 'Content-Type' => 'application/json'))
+  end
+
+  def test_detects_json
+    assert_equal :json_api, subject.detect('https://www.example.org/api', response_with('{}', 'Content-Type' => 'application/vnd.api+json'))
+  end
+
+  def test_detects_soap
+    body = '<?xml version="1.0"?><soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"></soap:Envelope>'
+    assert_equal :soap, subject.detect('https://www.example.org/ws', response_with(body, 'Content-Type' => 'text/xml'))
+  end
+
+  def test_detects_rss
+    assert_equal :rss, subject.detect('https://www.example.org/feed', response_with('<rss></rss>', 'Content-Type' => 'application/rss+xml'))
+  end
+
+  def test_detects_html
+    assert_equal :html, subject.detect('https://www.example.org/', response_with('<!DOCTYPE html>', 'Content-Type' => 'text/html'))
+  end
+
+  def test_detects_from_body_without_content_type
+    assert_equal :json_api, subject.detect('https://www.example.org/', response_with('[1, 2, 3]'))
+    assert_equal :html, subject.detect('https://www.example.org/', response_with('<html></html>'))
+    assert_equal :unknown, subject.detect('https://www.example.org/', response_with('hello'))
+  end
+
+  def test_hint_for_without_content_type
+    assert_nil subject.hint_for('https://www.example.org/', response_with(''))
+  end
+
+  def test_hint_from_allow
+    assert_equal :json_api, subject.hint_from_allow('GET, HEAD, PATCH')
+    assert_nil subject.hint_from_allow('GET, HEAD, POST')
+    assert_nil subject.hint_from_allow(nil)
+  end
+
+  def test_graphql
+    assert subject.graphql?(response_with('{"data":{"__typename":"Query"}}'))
+    refute subject.graphql?(response_with('{"data":[]}'))
+    refute subject.graphql?(response_with('<html></html>'))
+  end
+end
@@ -0,0 +1,74 @@
+require 'test_helper'
+
+class AuthenticatorTest < Minitest::Test
+  attr_reader :subject
+
+  def initialize(*args)
+    super
+    @subject = Net::Hippie::Authenticator.new(username: 'Mufasa', password: 'Circle Of Life')
+  end
+
+  def test_challenges_in
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Basic realm="test"')
+    response.add_field('WWW-Authenticate', 'Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093"')
+
+    expected = [
+      ['Basic', { 'realm' => 'test' }],
+      ['Digest', { 'realm' => 'testrealm@host.com', 'qop' => 'auth,auth-int', 'nonce' => 'dcd98b7102dd2f0e8b11d0f600bfb0c093' }]
+    ]
+    assert_equal expected, subject.challenges_in(response)
+  end
+
+  def test_challenges_in_a_single_header
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Basic realm="simple", Bearer realm="example", error="invalid_token", error_description="The \\"token\\" expired"')
+    response.add_field('WWW-Authenticate', 'Negotiate YIIB0gYGKwYBBQUCoII=')
+
+    expected = [
+      ['Basic', { 'realm' => 'simple' }],
+      ['Bearer', { 'realm' => 'example', 'error' => 'invalid_token', 'error_description' => 'The "token" expired' }],
+      ['Negotiate', { 'token68' => 'YIIB0gYGKwYBBQUCoII=' }]
+    ]
+    assert_equal expected, subject.challenges_in(response)
+  end
+
+  def test_digest_without_qop
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41"')
+
+    result = subject.authorization_for('GET', 'http://www.example.org/dir/index.html', response)
+    assert_equal 'Digest username="Mufasa", realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", uri="/dir/index.html", response="670fd8c2df070c60b045671b8b24ff02", opaque="5ccc069c403ebaf9f0171e9517f40e41"', result
+  end
+
+  def test_ignores_responses_that_are_not_unauthorized
+    response = Net::HTTPOK.new('1.1', '200', 'OK')
+    response.add_field('WWW-Authenticate', 'Basic realm="test"')
+
+    assert_nil subject.authorization_for('GET', 'http://www.example.org/', response)
+  end
+
+  def test_falls_back_to_basic_when_digest_uses_sha_256
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Digest realm="test", qop="auth", algorithm=SHA-256, nonce="abc"')
+    response.add_field('WWW-Authenticate', 'Basic realm="test"')
+
+    result = subject.authorization_for('GET', 'http://www.example.org/', response)
+    assert_equal Net::Hippie.basic_auth('Mufasa', 'Circle Of Life'), result
+  end
+
+  def test_falls_back_to_basic_when_digest_only_offers_auth_int
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Digest realm="test", qop="auth-int", nonce="abc", Basic realm="test"')
+
+    result = subject.authorization_for('GET', 'http://www.example.org/', response)
+    assert_equal Net::Hippie.basic_auth('Mufasa', 'Circle Of Life'), result
+  end
+
+  def test_skips_unsupported_digest_without_fallback
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Digest realm="test", algorithm=SHA-512-256, nonce="abc"')
+
+    assert_nil subject.authorization_for('GET', 'http://www.example.org/', response)
+  end
+end
@@ -0,0 +1,73 @@
+require 'test_helper'
+
+class AwsSigv4Test < Minitest::Test
+  attr_reader :subject
+
+  def initialize(*args)
+    super
+    @subject = Net::Hippie::AwsSigv4.new(
+      region: 'us-east-1',
+      service: 'service',
+      access_key_id: 'AKIDEXAMPLE',
+      secret_access_key: 'wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY'
+    )
+  end
+
+  def test_get_vanilla
+    request = Net::HTTP::Get.new(
+      URI.parse('https://example.amazonaws.com/'),
+      'X-Amz-Date' => '20150830T123600Z'
+    )
+
+    expected = 'AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, ' \
+      'SignedHeaders=host;x-amz-date, ' \
+      'Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31'
+    assert_equal expected, subject.authorization_for(request)
+  end
+
+  def test_get_vanilla_query_order_key_case
+    request = Net::HTTP::Get.new(
+      URI.parse('https://example.amazonaws.com/?Param2=value2&Param1=value1'),
+      'X-Amz-Date' => '20150830T123600Z'
+    )
+
+    expected = 'AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, ' \
+      'SignedHeaders=host;x-amz-date, ' \
+      'Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500'
+    assert_equal expected, subject.authorization_for(request)
+  end
+
+  def test_sign
+    request = Net::HTTP::Post.new(URI.parse('https://example.amazonaws.com/'))
+    request.body = 'Param1=value1'
+    subject.sign(request, Time.utc(2015, 8, 30, 12, 36, 0))
+
+    assert_equal '20150830T123600Z', request['x-amz-date']
+    assert_equal OpenSSL::Digest::SHA256.hexdigest('Param1=value1'), request['x-amz-content-sha256']
+    assert_match(/SignedHeaders=host;x-amz-content-sha256;x-amz-date,/, request['Authorization'])
+  end
+
+  def test_sign_streamed_body
+    request = Net::HTTP::Post.new(URI.parse('https://example.amazonaws.com/'))
+    request.body_stream = StringIO.new('Param1=value1')
+    subject.sign(request, Time.utc(2015, 8, 30, 12, 36, 0))
+
+    assert_equal 'UNSIGNED-PAYLOAD', request['x-amz-content-sha256']
+    assert_match(/SignedHeaders=host;x-amz-content-sha256;x-amz-date,/, request['Authorization'])
+  end
+
+  def test_sign_with_session_token
+    subject = Net::Hippie::AwsSigv4.new(
+      region: 'us-east-1',
+      service: 's3',
+      access_key_id: 'AKIDEXAMPLE',
+      secret_access_key: 'secret',
+      session_token: 'token'
+    )
+    request = Net::HTTP::Get.new(URI.parse('https://example.amazonaws.com/'))
+    subject.sign(request)
+
+    assert_equal 'token', request['x-amz-security-token']
+    assert_match(/x-amz-security-token/, request['Authorization'])
+  end
+end
@@ -0,0 +1,57 @@
+require 'test_helper'
+require 'minitest/mock'
+
+class CertificateTransparencyTest < Minitest::Test
+  StoreContext = Struct.new(:error_depth, :current_cert)
+
+  def build_certificate(extensions = [])
+    private_key = OpenSSL::PKey::RSA.new(2048)
+    OpenSSL::X509::Certificate.new.tap do |x|
+      x.not_before = Time.now
+      x.not_after = Time.now + 60
+      x.public_key = private_key.public_key
+      extensions.each { |extension| x.add_extension(extension) }
+      x.sign(private_key, OpenSSL::Digest::SHA256.new)
+    end
+  end
+
+  def sct_extension
+    OpenSSL::X509::Extension.new('1.3.6.1.4.1.11129.2.4.2', OpenSSL::ASN1::OctetString.new("\x00\x00").to_der)
+  end
+
+  def test_accepts_certificate_with_embedded_scts
+    subject = Net::Hippie::CertificateTransparency.new
+    assert subject.call(true, StoreContext.new(0, build_certificate([sct_extension])))
+    refute subject.rejected?
+  end
+
+  def test_rejects_certificate_without_embedded_scts
+    subject = Net::Hippie::CertificateTransparency.new
+    refute subject.call(true, StoreContext.new(0, build_certificate))
+    assert subject.rejected?
+  end
+
+  def test_ignores_intermediate_certificates
+    subject = Net::Hippie::CertificateTransparency.new
+    assert subject.call(true, StoreContext.new(1, build_certificate))
+    refute subject.rejected?
+  end
+
+  def test_preserves_failed_verification
+    subject = Net::Hippie::CertificateTransparency.new
+    refute subject.call(false, StoreContext.new(0, build_certificate([sct_extension])))
+    refute subject.rejected?
+  end
+
+  def test_client_raises_when_certificate_lacks_ct_proof
+    subject = Net::Hippie::Connection.new('https', 'www.example.org', 443, verify_ct: true)
+    http = subject.instance_variable_get(:@http)
+    transparency = subject.instance_variable_get(:@transparency)
+    http.stub(:request, ->(_) { transparency.call(true, StoreContext.new(0, build_certificate)); raise OpenSSL::SSL::SSLError }) do
+      error = assert_raises Net::Hippie::SSLError do
+        subject.run(Net::HTTP::Get.new(URI.parse('https://www.example.org/')))
+      end
+      assert_equal 'Certificate lacks CT proof', error.message
+    end
+  end
+end
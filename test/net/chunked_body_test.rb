@@ -0,0 +1,21 @@
+require 'test_helper'
+
+class ChunkedBodyTest < Minitest::Test
+  def test_read_in_chunks
+    chunks = %w[abc de f]
+    subject = Net::Hippie::ChunkedBody.new(2) { chunks.shift }
+
+    assert_equal 'ab', subject.read(4)
+    assert_equal 'cd', subject.read(4)
+    assert_equal 'ef', subject.read(4)
+    assert_nil subject.read(4)
+  end
+
+  def test_read_everything
+    chunks = %w[abc de f]
+    subject = Net::Hippie::ChunkedBody.new(2) { chunks.shift }
+
+    assert_equal 'abcdef', subject.read
+    assert_equal '', subject.read
+  end
+end
@@ -1,4 +1,7 @@
 require 'test_helper'
+require 'minitest/mock'
+require 'tempfile'
+require 'tmpdir'
 
 class ClientTest < Minitest::Test
   attr_reader :subject
@@ -303,4 +306,1050 @@ class ClientTest < Minitest::Test
     end
     assert(@called)
   end
+
+  def test_get_with_aws_sigv4
+    uri = URI.parse('https://example.amazonaws.com/')
+    WebMock.stub_request(:get, uri.to_s)
+      .with(headers: { 'Authorization' => /\AAWS4-HMAC-SHA256 Credential=AKIDEXAMPLE\/\d{8}\/us-east-1\/s3\/aws4_request/ })
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(aws_sigv4: {
+      region: 'us-east-1',
+      service: 's3',
+      access_key_id: 'AKIDEXAMPLE',
+      secret_access_key: 'wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY'
+    })
+    response = subject.get(uri)
+    refute_nil response
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_post_chunked_with_aws_sigv4
+    uri = 'https://examplebucket.s3.amazonaws.com/upload'
+    WebMock.stub_request(:post, uri)
+      .with(headers: {
+        'x-amz-content-sha256' => 'UNSIGNED-PAYLOAD',
+        'Authorization' => /SignedHeaders=host;x-amz-content-sha256;x-amz-date,/
+      })
+      .to_return(status: 200)
+
+    subject = Net::Hippie::Client.new(aws_sigv4: {
+      region: 'us-east-1',
+      service: 's3',
+      access_key_id: 'AKIDEXAMPLE',
+      secret_access_key: 'wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY'
+    })
+    chunks = %w[hello world]
+    response = subject.post_chunked(uri) { chunks.shift }
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_negotiate_auth_with_basic_challenge
+    uri = 'https://www.example.org/protected'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 401, headers: { 'WWW-Authenticate' => 'Basic realm="test"' })
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Authorization' => Net::Hippie.basic_auth('hippie', 'peace') })
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(auth: { username: 'hippie', password: 'peace' })
+    response = subject.negotiate_auth(uri)
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_negotiate_auth_prefers_digest
+    uri = 'https://www.example.org/protected'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 401, headers: { 'WWW-Authenticate' => ['Basic realm="test"', 'Digest realm="test", qop="auth", nonce="abc"'] })
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Authorization' => /\ADigest username="hippie", realm="test", nonce="abc", uri="\/protected", response="\h{32}", qop=auth, nc=00000001, cnonce="\h{16}"\z/ })
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(auth: { username: 'hippie', password: 'peace' })
+    response = subject.negotiate_auth(uri)
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_negotiate_auth_with_unsupported_challenge
+    uri = 'https://www.example.org/protected'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 401, headers: { 'WWW-Authenticate' => 'Negotiate' })
+
+    subject = Net::Hippie::Client.new(auth: { username: 'hippie', password: 'peace' })
+    response = subject.negotiate_auth(uri)
+    assert_equal Net::HTTPUnauthorized, response.class
+    assert_equal %w[Digest Basic], subject.auth_schemes_supported
+  end
+
+  def test_does_not_follow_redirect_from_https_to_http
+    WebMock.stub_request(:get, 'https://www.example.org/downgrade')
+      .to_return(status: 301, headers: { 'Location' => 'http://www.example.org/downgrade' })
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3)
+    response = subject.get('https://www.example.org/downgrade')
+    assert_equal Net::HTTPMovedPermanently, response.class
+    WebMock.assert_not_requested :get, 'http://www.example.org/downgrade'
+  end
+
+  def test_raises_on_redirect_from_https_to_http
+    WebMock.stub_request(:get, 'https://www.example.org/')
+      .to_return(status: 301, headers: { 'Location' => 'http://www.example.org/' })
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3, raise_on_insecure_redirect: true)
+    assert_raises Net::Hippie::InsecureRedirect do
+      subject.get('https://www.example.org/')
+    end
+  end
+
+  def test_follows_redirect_from_https_to_http_when_allowed
+    WebMock.stub_request(:get, 'https://www.example.org/')
+      .to_return(status: 301, headers: { 'Location' => 'http://www.example.org/' })
+    WebMock.stub_request(:get, 'http://www.example.org/')
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3, allow_insecure_redirects: true)
+    response = subject.get('https://www.example.org/')
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_follows_redirect_from_http_to_https
+    WebMock.stub_request(:get, 'http://www.example.org/')
+      .to_return(status: 301, headers: { 'Location' => 'https://www.example.org/' })
+    WebMock.stub_request(:get, 'https://www.example.org/')
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3)
+    response = subject.get('http://www.example.org/')
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_follows_redirect_from_https_to_https
+    WebMock.stub_request(:get, 'https://www.example.org/')
+      .to_return(status: 301, headers: { 'Location' => 'https://www.example.com/' })
+    WebMock.stub_request(:get, 'https://www.example.com/')
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3)
+    response = subject.get('https://www.example.org/')
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_get_with_jitter
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+    delays = []
+
+    subject.stub(:sleep, ->(delay) { delays << delay }) do
+      1_000.times { subject.get_with_jitter(uri, jitter_ms: 100) }
+    end
+
+    assert_equal 1_000, delays.size
+    assert(delays.all? { |x| x >= 0 && x < 0.1 })
+    buckets = delays.group_by { |x| (x * 100).floor }
+    assert_equal 10, buckets.size
+    assert(buckets.values.all? { |x| x.size.between?(50, 150) })
+  end
+
+  def test_get_without_jitter
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+
+    subject.stub(:sleep, ->(_) { flunk 'should not sleep' }) do
+      response = subject.get_with_jitter(uri)
+      assert_equal Net::HTTPOK, response.class
+    end
+  end
+
+  def test_with_retry_honours_retry_after
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 429, headers: { 'Retry-After' => '1' }).then
+      .to_return(status: 200, body: {}.to_json)
+    delays = []
+
+    subject = Net::Hippie::Client.new(max_retry_after_secs: 60)
+    response = subject.stub(:sleep, ->(delay) { delays << delay }) do
+      subject.with_retry(retries: 3) { |client| client.get(uri) }
+    end
+
+    assert_equal Net::HTTPOK, response.class
+    assert_equal [1], delays
+  end
+
+  def test_with_retry_ignores_retry_after_by_default
+    uri = 'https://www.example.org/retry-after/default'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 429, headers: { 'Retry-After' => '1' })
+
+    response = subject.stub(:sleep, ->(_) { flunk 'should not sleep' }) do
+      subject.with_retry(retries: 3) { |client| client.get(uri) }
+    end
+
+    assert_equal Net::HTTPTooManyRequests, response.class
+    WebMock.assert_requested :get, uri, times: 1
+  end
+
+  def test_with_retry_raises_when_retry_after_is_too_long
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 503, headers: { 'Retry-After' => '120' })
+
+    subject = Net::Hippie::Client.new(max_retry_after_secs: 30)
+    assert_raises Net::Hippie::RateLimitedError do
+      subject.with_retry(retries: 3) { |client| client.get(uri) }
+    end
+  end
+
+  def test_with_retry_returns_rate_limited_response_after_last_attempt
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 429, headers: { 'Retry-After' => '1' })
+
+    subject = Net::Hippie::Client.new(max_retry_after_secs: 60)
+    response = subject.with_retry(retries: 0) { |client| client.get(uri) }
+    assert_equal Net::HTTPTooManyRequests, response.class
+  end
+
+  def test_get_with_redirect_history
+    urls = %w[
+      https://www.example.org/hops/1
+      https://www.example.org/hops/2
+      https://www.example.org/hops/3
+      https://www.example.org/hops/4
+    ]
+    urls.each_cons(2) do |from, to|
+      WebMock.stub_request(:head, from).to_return(status: 301, headers: { 'Location' => to })
+    end
+    WebMock.stub_request(:head, urls.last).to_return(status: 200)
+    WebMock.stub_request(:get, urls.last).to_return(status: 200, body: { success: true }.to_json)
+
+    response = subject.get_with_redirect_history(urls.first)
+    history = response.redirect_history
+
+    assert_equal '200', response.code
+    assert JSON.parse(response.body)['success']
+    assert_equal 3, history.size
+    assert_equal({ url: urls[0], status: 301, location: urls[1] }, history[0])
+    assert_equal({ url: urls[2], status: 301, location: urls[3] }, history[2])
+    WebMock.assert_not_requested :get, urls[0]
+  end
+
+  def test_get_text_encoding_normalised
+    uri = 'https://www.example.org/utf16.json'
+    body = "\xFF\xFE".b + '{"name":"café"}'.encode('UTF-16LE').b
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 200, body: body, headers: { 'Content-Type' => 'application/json' })
+
+    result = subject.get_text_encoding_normalised(uri)
+    assert_equal '{"name":"café"}', result
+    assert result.valid_encoding?
+    refute result.start_with?("\uFEFF")
+  end
+
+  def test_create_from_ruby_net_http_settings
+    net_http = Net::HTTP.new('www.example.org', 443, 'proxy.example.org', 8080)
+    net_http.open_timeout = 5
+    net_http.read_timeout = 7
+    net_http.verify_mode = OpenSSL::SSL::VERIFY_NONE
+
+    subject = Net::Hippie::Client.create_from_ruby_net_http_settings(net_http)
+    http = subject.send(:connection_for, 'https://www.example.org/').instance_variable_get(:@http)
+
+    assert_equal 'proxy.example.org', http.proxy_address
+    assert_equal 8080, http.proxy_port
+    assert_equal 5, http.open_timeout
+    assert_equal 7, http.read_timeout
+    assert_equal OpenSSL::SSL::VERIFY_NONE, http.verify_mode
+  end
+
+  def test_create_from_ruby_net_http_settings_prefers_given_options
+    net_http = Net::HTTP.new('www.example.org', 443)
+    net_http.open_timeout = 5
+    net_http.read_timeout = 7
+
+    subject = Net::Hippie::Client.create_from_ruby_net_http_settings(net_http, read_timeout: 2)
+    http = subject.send(:connection_for, 'https://www.example.org/').instance_variable_get(:@http)
+
+    assert_equal 5, http.open_timeout
+    assert_equal 2, http.read_timeout
+  end
+
+  def test_create_from_ruby_net_http_settings_with_client_certificate
+    private_key = OpenSSL::PKey::RSA.new(2048)
+    certificate = OpenSSL::X509::Certificate.new
+    certificate.not_after = certificate.not_before = Time.now
+    certificate.public_key = private_key.public_key
+    certificate.sign(private_key, OpenSSL::Digest::SHA256.new)
+    net_http = Net::HTTP.new('www.example.org', 443)
+    net_http.cert = certificate
+    net_http.key = private_key
+    output = StringIO.new
+
+    subject = Net::Hippie::Client.create_from_ruby_net_http_settings(net_http, logger: Logger.new(output))
+    http = subject.send(:connection_for, 'https://www.example.org/').instance_variable_get(:@http)
+
+    assert_equal certificate.to_pem, http.cert.to_pem
+    assert_equal private_key.to_pem, http.key.to_pem
+    refute_includes output.string, 'Ignoring'
+  end
+
+  def test_create_from_ruby_net_http_settings_with_write_timeout
+    net_http = Net::HTTP.new('www.example.org', 443)
+    skip 'Net::HTTP#write_timeout is not available' unless net_http.respond_to?(:write_timeout=)
+    net_http.write_timeout = 3
+
+    subject = Net::Hippie::Client.create_from_ruby_net_http_settings(net_http)
+    http = subject.send(:connection_for, 'https://www.example.org/').instance_variable_get(:@http)
+
+    assert_equal 3, http.write_timeout
+  end
+
+  def test_create_from_ruby_net_http_settings_with_verify_hostname
+    net_http = Net::HTTP.new('www.example.org', 443)
+    skip 'Net::HTTP#verify_hostname is not available' unless net_http.respond_to?(:verify_hostname=)
+    net_http.verify_hostname = false
+
+    subject = Net::Hippie::Client.create_from_ruby_net_http_settings(net_http)
+    http = subject.send(:connection_for, 'https://www.example.org/').instance_variable_get(:@http)
+
+    assert_equal false, http.verify_hostname
+  end
+
+  def test_create_from_ruby_net_http_settings_warns_about_unsupported_settings
+    net_http = Net::HTTP.new('www.example.org', 443)
+    net_http.ciphers = 'ALL'
+    output = StringIO.new
+
+    Net::Hippie::Client.create_from_ruby_net_http_settings(net_http, logger: Logger.new(output))
+    assert_includes output.string, 'Ignoring unsupported Net::HTTP setting `ciphers`'
+  end
+
+  def test_get_with_forwarded_for
+    uri = 'https://www.example.org/forwarded'
+    WebMock.stub_request(:get, uri)
+      .with(headers: {
+        'X-Forwarded-For' => '203.0.113.1, 198.51.100.2',
+        'X-Forwarded-Host' => 'www.example.org',
+        'X-Forwarded-Proto' => 'https',
+        'Forwarded' => 'for=203.0.113.1;host=www.example.org;proto=https, for=198.51.100.2'
+      })
+      .to_return(status: 200, body: {}.to_json)
+
+    subject = Net::Hippie::Client.new(forwarded_for: ['203.0.113.1', '198.51.100.2'])
+    response = subject.get(uri)
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_introspect_server
+    uri = 'https://www.example.org/introspect'
+    WebMock.stub_request(:head, uri)
+      .to_return(status: 200, headers: {
+        'Server' => 'nginx/1.19.6',
+        'X-Powered-By' => 'Express',
+        'Content-Type' => 'application/json; charset=utf-8',
+        'Content-Encoding' => 'gzip',
+        'Accept-Patch' => 'application/merge-patch+json, application/json-patch+json',
+        'WWW-Authenticate' => 'Bearer realm="api"',
+        'Access-Control-Allow-Origin' => '*'
+      })
+
+    result = subject.introspect_server(uri)
+    assert_equal 'nginx/1.19.6', result[:server]
+    assert_equal 'Express', result[:powered_by]
+    refute_nil result[:http_version]
+    assert_equal ['gzip'], result[:compression]
+    assert_equal ['Bearer'], result[:auth_schemes]
+    assert result[:cors_enabled]
+    refute result[:hsts_enabled]
+    assert_equal %w[application/json application/merge-patch+json application/json-patch+json], result[:content_types_supported]
+  end
+
+  def test_execute_with_tracing_context
+    uri = 'https://www.example.org/traced'
+    WebMock.stub_request(:post, uri)
+      .with(headers: { 'traceparent' => '00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01' })
+      .to_return(status: 201, body: {}.to_json)
+
+    span_context = { trace_id: '4bf92f3577b34da6a3ce929d0e0e4736', parent_id: '00f067aa0ba902b7', flags: '01' }
+    response = subject.execute_with_tracing_context(span_context, Net::HTTP::Post, uri, body: { name: 'hippie' })
+    assert_equal Net::HTTPCreated, response.class
+  end
+
+  def test_get_csv
+    uri = 'https://www.example.org/export.csv'
+    body = "id,name\n" + (1..10_000).map { |x| "#{x},hippie #{x}\n" }.join
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Accept' => 'text/csv' })
+      .to_return(status: 200, body: body, headers: { 'Content-Type' => 'text/csv' })
+
+    rows = subject.get_csv(uri)
+    assert_equal 10_000, rows.size
+    assert_equal({ 'id' => '1', 'name' => 'hippie 1' }, rows.first)
+    assert_equal({ 'id' => '10000', 'name' => 'hippie 10000' }, rows.last)
+  end
+
+  def test_get_csv_with_block
+    uri = 'https://www.example.org/export.csv'
+    body = (1..10_000).map { |x| "#{x};hippie #{x}\n" }.join
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: body)
+
+    count = 0
+    last = nil
+    result = subject.get_csv(uri, delimiter: ';', has_headers: false) do |row|
+      count += 1
+      last = row
+    end
+    assert_equal 10_000, count
+    assert_equal ['10000', 'hippie 10000'], last
+    assert_empty result
+  end
+
+  def test_detect_api_type_from_head
+    uri = 'https://head.example.org/detect'
+    WebMock.stub_request(:head, uri).to_return(status: 200, headers: { 'Content-Type' => 'application/json' })
+
+    assert_equal :json_api, subject.detect_api_type(uri)
+    WebMock.assert_not_requested :options, uri
+  end
+
+  def test_detect_api_type_from_allow
+    uri = 'https://allow.example.org/widgets/1'
+    WebMock.stub_request(:head, uri).to_return(status: 200)
+    WebMock.stub_request(:options, uri).to_return(status: 204, headers: { 'Allow' => 'GET, PUT, DELETE' })
+
+    assert_equal :json_api, subject.detect_api_type(uri)
+  end
+
+  def test_detect_api_type_from_graphql_probe
+    uri = 'https://graphql.example.org/api'
+    WebMock.stub_request(:head, uri).to_return(status: 405)
+    WebMock.stub_request(:options, uri).to_return(status: 204, headers: { 'Allow' => 'GET, POST' })
+    WebMock.stub_request(:get, 'https://graphql.example.org/graphql?query=%7B__typename%7D')
+      .to_return(status: 200, headers: { 'Content-Type' => 'application/json' }, body: '{"data":{"__typename":"Query"}}')
+
+    assert_equal :graphql, subject.detect_api_type(uri)
+  end
+
+  def test_detect_api_type_from_body_sample
+    uri = 'https://sample.example.org/detect'
+    WebMock.stub_request(:head, uri).to_return(status: 200)
+    WebMock.stub_request(:options, uri).to_return(status: 405)
+    WebMock.stub_request(:get, 'https://sample.example.org/graphql?query=%7B__typename%7D').to_return(status: 404)
+    WebMock.stub_request(:get, 'https://sample.example.org/.well-known/graphql?query=%7B__typename%7D').to_return(status: 404)
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Range' => 'bytes=0-511' })
+      .to_return(status: 206, body: '{"data":')
+
+    assert_equal :json_api, subject.detect_api_type(uri)
+  end
+
+  def test_on_before_request_hook_adds_header
+    uri = 'https://www.example.org/hooks'
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'X-Request-Id' => 'abc123' })
+      .to_return(status: 200, body: {}.to_json)
+
+    subject.on_before_request { |request| request['X-Request-Id'] = 'abc123' }
+    response = subject.get(uri)
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_on_after_response_hook
+    uri = 'https://www.example.org/hooks'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+    calls = []
+
+    subject.on_after_response { |response, elapsed_ms| calls << [response.code, elapsed_ms] }
+    subject.get(uri)
+    assert_equal 1, calls.size
+    assert_equal '200', calls[0][0]
+    assert calls[0][1] >= 0
+  end
+
+  def test_on_error_hook
+    uri = 'https://www.example.org/hooks/timeout'
+    WebMock.stub_request(:get, uri).to_timeout
+    errors = []
+
+    subject.on_error { |message, request| errors << [message, request.path] }
+    assert_raises Net::OpenTimeout do
+      subject.get(uri)
+    end
+    assert_equal 1, errors.size
+    assert_equal '/hooks/timeout', errors[0][1]
+  end
+
+  def test_hooks_require_a_block
+    %i[on_before_request on_after_response on_error].each do |name|
+      assert_raises ArgumentError do
+        subject.public_send(name)
+      end
+    end
+  end
+
+  def test_throttled_get_all_runs_hooks
+    urls = Array.new(3) { |n| "https://www.example.org/throttled/hooks/#{n}" }
+    urls.each { |url| WebMock.stub_request(:get, url).with(headers: { 'X-Request-Id' => 'abc123' }) }
+    paths = Queue.new
+
+    subject.on_before_request { |request| request['X-Request-Id'] = 'abc123' }
+    subject.on_after_response { |response, _| paths << response.code }
+    results = subject.throttled_get_all(urls, rps: 100, concurrency: 2)
+
+    assert_equal %w[200 200 200], results.map { |x| x[:response].code }
+    assert_equal 3, paths.size
+  end
+
+  def test_logs_each_response
+    uri = 'https://www.example.org/logged?token=secret'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+    output = StringIO.new
+
+    subject = Net::Hippie::Client.new(logger: Logger.new(output))
+    subject.get(uri)
+
+    assert_match(%r{INFO -- : GET https://www.example.org/logged 200 \d+ms$}, output.string)
+    refute_includes output.string, 'secret'
+  end
+
+  def test_logs_redacted_query_when_enabled
+    uri = 'https://www.example.org/logged/query?token=secret&page=2'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+    output = StringIO.new
+
+    subject = Net::Hippie::Client.new(logger: Logger.new(output), log_query: true, redact_query_params: ['token'])
+    subject.get(uri)
+
+    assert_includes output.string, 'GET https://www.example.org/logged/query?token=[REDACTED]&page=2 200'
+  end
+
+  def test_logs_errors
+    uri = 'https://www.example.org/logged/timeout'
+    WebMock.stub_request(:get, uri).to_timeout
+    output = StringIO.new
+
+    subject = Net::Hippie::Client.new(logger: Logger.new(output))
+    assert_raises Net::OpenTimeout do
+      subject.get(uri)
+    end
+
+    assert_match(%r{ERROR -- : GET https://www.example.org/logged/timeout Net::OpenTimeout}, output.string)
+  end
+
+  def test_debug_request
+    headers = { 'Authorization' => Net::Hippie.bearer_auth('secret'), 'X-Api-Key' => 'secret' }
+    result = subject.debug_request(Net::HTTP::Post, 'https://www.example.org/api', headers: headers, body: { name: 'hippie' })
+
+    assert result.start_with?("curl -X POST 'https://www.example.org/api' ")
+    assert_includes result, "-H 'Authorization: [REDACTED]'"
+    assert_includes result, "-H 'X-Api-Key: [REDACTED]'"
+    assert_includes result, "-H 'Accept: application/json'"
+    assert_includes result, %q(--data-raw '{"name":"hippie"}')
+    refute_includes result, 'secret'
+    WebMock.assert_not_requested :post, 'https://www.example.org/api'
+  end
+
+  def test_debug_request_without_redaction
+    headers = { 'Authorization' => Net::Hippie.bearer_auth('secret') }
+    result = subject.debug_request(Net::HTTP::Get, 'https://www.example.org/api', headers: headers, redact_sensitive: false)
+
+    assert_includes result, "-H 'Authorization: Bearer secret'"
+  end
+
+  def test_post_chunked
+    chunks = %w[a b c d e].map { |x| x * 1024 }
+    uri = 'https://www.example.org/chunked'
+    WebMock.stub_request(:post, uri)
+      .with(headers: { 'Transfer-Encoding' => 'chunked' }, body: chunks.join)
+      .to_return(status: 201)
+
+    remaining = chunks.dup
+    response = subject.post_chunked(uri) { remaining.shift }
+
+    assert_equal Net::HTTPCreated, response.class
+    assert_empty remaining
+  end
+
+  def test_content_digest
+    uri = 'https://www.example.org/digest'
+    WebMock.stub_request(:post, uri)
+      .with(headers: { 'Content-Digest' => 'sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:' })
+      .to_return(status: 201)
+
+    subject = Net::Hippie::Client.new(content_digest: [:sha256])
+    subject.post(uri, body: '{"hello": "world"}')
+
+    WebMock.assert_requested :post, uri
+  end
+
+  def test_get_with_exponential_retry
+    uri = 'https://www.example.org/flaky'
+    WebMock.stub_request(:get, uri)
+      .to_return(status: 503).times(3).then
+      .to_return(status: 200, body: 'ok')
+
+    delays = []
+    response = subject.stub(:sleep, ->(delay) { delays << delay }) do
+      subject.get_with_exponential_retry(uri, base_delay_ms: 100, jitter: false)
+    end
+
+    assert_equal Net::HTTPOK, response.class
+    assert_equal [0.1, 0.2, 0.4], delays
+  end
+
+  def test_get_with_exponential_retry_returns_the_last_response
+    uri = 'https://www.example.org/down'
+    WebMock.stub_request(:get, uri).to_return(status: 502)
+
+    delays = []
+    response = subject.stub(:sleep, ->(delay) { delays << delay }) do
+      subject.get_with_exponential_retry(uri, max_attempts: 3)
+    end
+
+    assert_equal Net::HTTPBadGateway, response.class
+    assert_equal 2, delays.size
+    assert_in_delta 0.1, delays[0], 0.01
+    assert_in_delta 0.2, delays[1], 0.02
+  end
+
+  def test_get_with_exponential_retry_raises_the_last_error
+    uri = 'https://www.example.org/unreachable'
+    WebMock.stub_request(:get, uri).to_raise(Errno::ECONNREFUSED)
+
+    subject.stub(:sleep, ->(_) {}) do
+      assert_raises Errno::ECONNREFUSED do
+        subject.get_with_exponential_retry(uri, max_attempts: 2)
+      end
+    end
+  end
+
+  def test_get_with_exponential_retry_redacts_logged_url
+    uri = 'https://www.example.org/unreachable/logged?api_key=secret'
+    WebMock.stub_request(:get, uri).to_raise(Errno::ECONNREFUSED).then.to_return(status: 200)
+    output = StringIO.new
+
+    subject = Net::Hippie::Client.new(logger: Logger.new(output), log_query: true, redact_query_params: ['api_key'])
+    subject.stub(:sleep, ->(_) {}) do
+      subject.get_with_exponential_retry(uri, max_attempts: 2)
+    end
+
+    assert_includes output.string, 'retrying https://www.example.org/unreachable/logged?api_key=[REDACTED]'
+    refute_includes output.string, 'secret'
+  end
+
+  def test_post_expecting
+    uri = 'https://www.example.org/account/12345/msgs'
+    problem = {
+      type: 'https://example.com/probs/out-of-credit',
+      title: 'You do not have enough credit.',
+      detail: 'Your current balance is 30, but that costs 50.',
+      instance: '/account/12345/msgs/abc',
+      balance: 30,
+      accounts: ['/account/12345', '/account/67890']
+    }
+    WebMock.stub_request(:post, uri).to_return(
+      status: 403,
+      headers: { 'Content-Type' => 'application/problem+json' },
+      body: JSON.generate(problem)
+    )
+
+    error = assert_raises Net::Hippie::ProblemDetailsError do
+      subject.post_expecting(uri, body: { message: 'hello' })
+    end
+
+    assert_equal 'You do not have enough credit.', error.message
+    assert_equal 'https://example.com/probs/out-of-credit', error.problem.type
+    assert_equal 'Your current balance is 30, but that costs 50.', error.problem.detail
+    assert_equal '/account/12345/msgs/abc', error.problem.instance
+    assert_nil error.problem.status
+    assert_equal({ 'balance' => 30, 'accounts' => ['/account/12345', '/account/67890'] }, error.problem.extensions)
+  end
+
+  def test_post_expecting_other_errors
+    uri = 'https://www.example.org/plain-error'
+    WebMock.stub_request(:post, uri).to_return(status: 500, headers: { 'Content-Type' => 'application/json' }, body: '{}')
+
+    assert_equal Net::HTTPInternalServerError, subject.post_expecting(uri).class
+  end
+
+  def test_get_protobuf
+    uri = 'https://www.example.org/person'
+    encoded = "\x0A\x06hippie\x10\x2A".b
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Accept' => 'application/x-protobuf' })
+      .to_return(status: 200, headers: { 'Content-Type' => 'application/x-protobuf' }, body: encoded)
+    message_class = Minitest::Mock.new
+    message_class.expect(:decode, :person, [encoded])
+
+    assert_equal :person, subject.get_protobuf(uri, message_class)
+    message_class.verify
+  end
+
+  def test_post_multipart_streaming
+    uri = 'https://www.example.org/upload'
+    content = SecureRandom.random_bytes(64 * 1024)
+    WebMock.stub_request(:post, uri).with do |request|
+      request.headers['Content-Type'].start_with?('multipart/form-data; boundary=') &&
+        request.headers['Content-Length'] == request.body.bytesize.to_s &&
+        request.body.b.include?(content.b)
+    end.to_return(status: 201)
+
+    response = Tempfile.create('upload') do |file|
+      file.binmode
+      file.write(content)
+      file.rewind
+      subject.post_multipart_streaming(uri, { 'name' => 'hippie', 'file' => file })
+    end
+
+    assert_equal Net::HTTPCreated, response.class
+  end
+
+  def test_post_form_with_file
+    uri = 'https://bucket.s3.amazonaws.com/'
+    WebMock.stub_request(:post, uri).with do |request|
+      request.body.include?(%(name="key"\r\n\r\nuploads/report.csv\r\n)) &&
+        request.body.include?(%(name="file"; filename="report.csv"\r\nContent-Type: text/csv\r\n\r\na,b\n1,2\n))
+    end.to_return(status: 204)
+
+    response = Dir.mktmpdir do |dir|
+      path = File.join(dir, 'report.csv')
+      File.write(path, "a,b\n1,2\n")
+      subject.post_form_with_file(uri, { 'key' => 'uploads/report.csv' }, 'file', path)
+    end
+
+    assert_equal Net::HTTPNoContent, response.class
+  end
+
+  def test_post_form_with_file_content_type_field
+    uri = 'https://bucket.s3.amazonaws.com/typed'
+    WebMock.stub_request(:post, uri).with do |request|
+      request.body.include?(%(name="Content-Type"\r\n\r\ntext/plain\r\n)) &&
+        request.body.include?(%(filename="report.csv"\r\nContent-Type: text/plain\r\n))
+    end.to_return(status: 204)
+
+    response = Dir.mktmpdir do |dir|
+      path = File.join(dir, 'report.csv')
+      File.write(path, "a,b\n")
+      subject.post_form_with_file(uri, { 'Content-Type' => 'text/plain' }, 'file', path)
+    end
+
+    assert_equal Net::HTTPNoContent, response.class
+  end
+
+  def test_get_geojson
+    uri = 'https://www.example.org/places'
+    WebMock.stub_request(:get, uri).to_return(
+      status: 200,
+      headers: { 'Content-Type' => 'application/geo+json' },
+      body: JSON.generate(type: 'FeatureCollection', features: [
+        { type: 'Feature', geometry: { type: 'Point', coordinates: [-114.07, 51.05] }, properties: { name: 'Calgary' } },
+        { type: 'Feature', geometry: { type: 'LineString', coordinates: [[-113.49, 53.54], [-123.12, 49.28]] }, properties: { name: 'Route' } },
+        { type: 'Feature', geometry: nil, properties: nil }
+      ])
+    )
+
+    result = subject.get_geojson(uri)
+
+    assert_equal 3, result.feature_count
+    assert_equal [-123.12, 49.28, -113.49, 53.54], result.bounding_box
+    assert_equal 'Point', result.features[0].geometry_type
+    assert_equal [-114.07, 51.05], result.features[0].coordinates
+    assert_equal({ 'name' => 'Route' }, result.features[1].properties)
+    assert_nil result.features[2].geometry_type
+    assert_equal({}, result.features[2].properties)
+  end
+
+  def test_get_geojson_with_unexpected_content_type
+    uri = 'https://www.example.org/not-geo'
+    WebMock.stub_request(:get, uri).to_return(status: 200, headers: { 'Content-Type' => 'text/html' }, body: '<html>')
+
+    assert_raises Net::Hippie::UnexpectedContentTypeError do
+      subject.get_geojson(uri)
+    end
+  end
+
+  def test_head_check
+    {
+      200 => :ok, 204 => :ok, 301 => :redirect, 401 => :forbidden, 403 => :forbidden,
+      404 => :not_found, 405 => :client_error, 418 => :client_error, 429 => :client_error,
+      500 => :server_error, 503 => :server_error
+    }.each do |status, expected|
+      uri = "https://www.example.org/head-check/#{status}"
+      WebMock.stub_request(:head, uri).to_return(status: status)
+
+      assert_equal expected, subject.head_check(uri), status
+    end
+  end
+
+  def test_head_check_does_not_follow_redirects
+    uri = 'https://www.example.org/head-check/moved'
+    WebMock.stub_request(:head, uri).to_return(status: 302, headers: { 'Location' => '/head-check/elsewhere' })
+
+    subject = Net::Hippie::Client.new(follow_redirects: 3)
+    assert_equal :redirect, subject.head_check(uri)
+    WebMock.assert_not_requested(:get, 'https://www.example.org/head-check/elsewhere')
+  end
+
+  def test_head_check_connection_refused
+    uri = 'https://www.example.org/head-check/refused'
+    WebMock.stub_request(:head, uri).to_raise(Errno::ECONNREFUSED)
+
+    assert_equal :error, subject.head_check(uri)
+  end
+
+  def test_send_beacon
+    uri = 'https://www.example.org/beacon'
+    received = Queue.new
+    WebMock.stub_request(:post, uri)
+      .with(headers: { 'Content-Type' => 'text/plain', 'User-Agent' => 'net-hippie-beacon' })
+      .to_return { |request| received << request.body; { status: 204 } }
+
+    assert subject.send_beacon(uri, 'event=click')
+    assert_equal 'event=click', Timeout.timeout(1) { received.pop }
+  end
+
+  def test_send_beacon_does_not_wait_for_the_response
+    uri = 'https://www.example.org/beacon/slow'
+    received = Queue.new
+    WebMock.stub_request(:post, uri).to_return do |request|
+      sleep 1
+      received << request.body
+      { status: 204 }
+    end
+
+    started_at = Process.clock_gettime(Process::CLOCK_MONOTONIC)
+    assert subject.send_beacon(uri, 'event=view')
+    assert_operator Process.clock_gettime(Process::CLOCK_MONOTONIC) - started_at, :<, 0.5
+    assert_equal 'event=view', Timeout.timeout(3) { received.pop }
+  end
+
+  def test_send_beacon_bounds_each_timeout
+    uri = 'https://www.example.org/beacon/timeouts'
+    WebMock.stub_request(:post, uri).to_return(status: 204)
+    options = nil
+    client_with = subject.method(:client_with)
+
+    subject.stub(:client_with, ->(x) { options = x; client_with.call(x) }) do
+      assert subject.send_beacon(uri, 'event=click')
+    end
+    assert_equal 5, options[:open_timeout]
+    assert_equal 5, options[:read_timeout]
+    assert_equal 5, options[:write_timeout]
+  end
+
+  def test_send_beacon_ignores_errors
+    uri = 'https://www.example.org/beacon-down'
+    WebMock.stub_request(:post, uri).to_raise(Errno::ECONNREFUSED)
+
+    assert subject.send_beacon(uri, 'event=click')
+  end
+
+  def test_throttled_get_all
+    urls = Array.new(6) { |n| "https://www.example.org/throttled/#{n}" }
+    urls.each_with_index { |url, n| WebMock.stub_request(:get, url).to_return(status: 200, body: n.to_s) }
+    urls << 'https://www.example.org/throttled/refused'
+    WebMock.stub_request(:get, urls.last).to_raise(Errno::ECONNREFUSED)
+
+    started_at = Process.clock_gettime(Process::CLOCK_MONOTONIC)
+    results = subject.throttled_get_all(urls, rps: 20, concurrency: 3)
+    elapsed = Process.clock_gettime(Process::CLOCK_MONOTONIC) - started_at
+
+    assert_operator elapsed, :>=, 0.3
+    assert_equal urls, results.map { |x| x[:url] }
+    assert_equal %w[0 1 2 3 4 5], results.first(6).map { |x| x[:response].body }
+    assert_nil results.last[:response]
+    assert_match(/refused/i, results.last[:error])
+  end
+
+  def test_purge
+    uri = 'https://cache.example.org/assets/app.js'
+    WebMock.stub_request(:purge, uri).to_return(status: 200, body: 'Purged')
+
+    response = subject.purge(uri)
+
+    assert_equal Net::HTTPOK, response.class
+    assert_equal 'Purged', response.body
+  end
+
+  def test_ban
+    uri = 'https://cache.example.org/'
+    WebMock.stub_request(:ban, uri)
+      .with(headers: { 'X-Ban-Url' => '^/assets/' })
+      .to_return(status: 200)
+
+    assert_equal Net::HTTPOK, subject.ban(uri, headers: { 'X-Ban-Url' => '^/assets/' }).class
+  end
+
+  def test_get_with_max_age_check
+    uri = 'https://www.example.org/fresh'
+    WebMock.stub_request(:get, uri).to_return(status: 200, headers: { 'Last-Modified' => (Time.now - 60).httpdate })
+
+    assert_equal Net::HTTPOK, subject.get_with_max_age_check(uri, max_age_secs: 300).class
+  end
+
+  def test_get_with_max_age_check_stale
+    uri = 'https://www.example.org/stale'
+    last_modified_at = Time.at((Time.now - 600).to_i)
+    WebMock.stub_request(:get, uri).to_return(status: 200, headers: { 'Last-Modified' => last_modified_at.httpdate })
+
+    error = assert_raises Net::Hippie::StaleResourceError do
+      subject.get_with_max_age_check(uri, max_age_secs: 300)
+    end
+    assert_equal last_modified_at, error.last_modified_at
+    assert_equal 300, error.max_age_secs
+  end
+
+  def test_get_with_max_age_check_without_last_modified
+    uri = 'https://www.example.org/undated'
+    WebMock.stub_request(:get, uri).to_return(status: 200)
+
+    assert_raises Net::Hippie::MissingLastModifiedError do
+      subject.get_with_max_age_check(uri, max_age_secs: 300)
+    end
+  end
+
+  def test_post_application_json_patch
+    uri = 'https://www.example.org/users/1'
+    operations = [{ op: 'replace', path: '/name', value: 'hippie' }]
+    WebMock.stub_request(:patch, uri)
+      .with(headers: { 'Content-Type' => 'application/json-patch+json' }, body: JSON.generate(operations))
+      .to_return(status: 204)
+
+    assert_equal Net::HTTPNoContent, subject.post_application_json_patch(uri, operations).class
+  end
+
+  def test_post_application_json_patch_rejected
+    uri = 'https://www.example.org/users/2'
+    WebMock.stub_request(:patch, uri).to_return(
+      status: 409,
+      headers: { 'Content-Type' => 'application/problem+json' },
+      body: JSON.generate(type: 'https://example.org/probs/conflict', title: 'Patch test failed')
+    )
+
+    error = assert_raises Net::Hippie::ProblemDetailsError do
+      subject.post_application_json_patch(uri, [{ op: 'test', path: '/name', value: 'hippie' }])
+    end
+    assert_equal 'Patch test failed', error.message
+  end
+
+  def test_post_multipart_presigned_s3
+    uri = 'https://hippie-bucket.s3.amazonaws.com/'
+    WebMock.stub_request(:post, uri).with do |request|
+      request.body.index('name="policy"') < request.body.index('name="file"; filename="report.csv"')
+    end.to_return(status: 204, headers: { 'Location' => 'https://hippie-bucket.s3.amazonaws.com/uploads%2Freport.csv' })
+
+    result = Dir.mktmpdir do |dir|
+      path = File.join(dir, 'report.csv')
+      File.write(path, "a,b\n")
+      fields = { 'key' => 'uploads/report.csv', 'AWSAccessKeyId' => 'AKIDEXAMPLE', 'policy' => 'eyJ9', 'signature' => 'c2ln' }
+      subject.post_multipart_presigned_s3(fields, path, uri)
+    end
+
+    expected = { success: true, error: nil, location: 'https://hippie-bucket.s3.amazonaws.com/uploads%2Freport.csv' }
+    assert_equal expected, result
+  end
+
+  def test_post_multipart_presigned_s3_error
+    uri = 'https://hippie-bucket.s3.amazonaws.com/denied'
+    WebMock.stub_request(:post, uri).to_return(
+      status: 403,
+      headers: { 'Content-Type' => 'application/xml' },
+      body: '<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Invalid according to Policy</Message></Error>'
+    )
+
+    result = Dir.mktmpdir do |dir|
+      path = File.join(dir, 'report.csv')
+      File.write(path, "a,b\n")
+      subject.post_multipart_presigned_s3({ 'key' => 'uploads/report.csv' }, path, uri)
+    end
+
+    assert_equal({ success: false, error: 'AccessDenied: Invalid according to Policy', location: nil }, result)
+  end
+
+  def test_post_soap
+    uri = 'https://www.example.org/soap'
+    envelope = '<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body/></soap:Envelope>'
+    WebMock.stub_request(:post, uri)
+      .with(body: envelope, headers: { 'Content-Type' => 'text/xml; charset=utf-8', 'SOAPAction' => '"urn:GetQuote"' })
+      .to_return(status: 200, headers: { 'Content-Type' => 'text/xml' }, body: envelope)
+
+    response = subject.post_soap(uri, 'urn:GetQuote', envelope)
+    assert_equal '200', response.code
+    refute response.fault?
+    assert_nil response.fault_message
+  end
+
+  def test_post_soap_with_fault
+    uri = 'https://www.example.org/soap/fault'
+    fault = <<~XML
+      <soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+        <soap:Body>
+          <soap:Fault>
+            <faultcode>soap:Server</faultcode>
+            <faultstring>Quote service unavailable</faultstring>
+          </soap:Fault>
+        </soap:Body>
+      </soap:Envelope>
+    XML
+    WebMock.stub_request(:post, uri).to_return(status: 500, headers: { 'Content-Type' => 'text/xml' }, body: fault)
+
+    response = subject.post_soap(uri, 'urn:GetQuote', '<soap:Envelope/>')
+    assert response.fault?
+    assert_equal 'Quote service unavailable', response.fault_message
+  end
+
+  def test_post_soap_12
+    uri = 'https://www.example.org/soap12'
+    envelope = '<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Body/></env:Envelope>'
+    fault = <<~XML
+      <env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+        <env:Body>
+          <env:Fault>
+            <env:Code><env:Value>env:Sender</env:Value></env:Code>
+            <env:Reason><env:Text xml:lang="en">Unknown symbol</env:Text></env:Reason>
+          </env:Fault>
+        </env:Body>
+      </env:Envelope>
+    XML
+    WebMock.stub_request(:post, uri)
+      .with(headers: { 'Content-Type' => 'application/soap+xml; charset=utf-8; action="urn:GetQuote"' })
+      .to_return(status: 400, headers: { 'Content-Type' => 'application/soap+xml' }, body: fault)
+
+    response = subject.post_soap(uri, 'urn:GetQuote', envelope)
+    assert response.fault?
+    assert_equal 'Unknown symbol', response.fault_message
+  end
+
+  def test_get_with_fallback_body_on_connection_error
+    uri = 'https://unreachable.example.org/widget'
+    WebMock.stub_request(:get, uri).to_raise(Errno::ECONNREFUSED)
+
+    response = subject.get_with_fallback_body(uri, 'cached content')
+
+    assert response.fallback?
+    assert Net::Hippie.fallback?(response)
+    assert_equal '0', response.code
+    assert_equal 'cached content', response.body
+  end
+
+  def test_get_with_fallback_body_on_server_error
+    uri = 'https://www.example.org/widget/broken'
+    WebMock.stub_request(:get, uri).to_return(status: [502, 'Bad Gateway'])
+
+    response = subject.get_with_fallback_body(uri, 'cached content')
+
+    assert response.fallback?
+    assert_equal 'cached content', response.body
+    assert_equal '502 Bad Gateway', response.message
+  end
+
+  def test_get_with_fallback_body_on_success
+    uri = 'https://www.example.org/widget'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: 'fresh content')
+
+    response = subject.get_with_fallback_body(uri, 'cached content')
+
+    refute Net::Hippie.fallback?(response)
+    assert_equal 'fresh content', response.body
+  end
 end
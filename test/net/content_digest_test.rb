@@ -0,0 +1,34 @@
+require 'test_helper'
+
+class ContentDigestTest < Minitest::Test
+  BODY = '{"hello": "world"}'.freeze
+  SHA256 = 'sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:'.freeze
+  SHA512 = 'sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:'.freeze
+
+  def test_header_for
+    assert_equal SHA256, Net::Hippie::ContentDigest.new.header_for(BODY)
+    assert_equal "#{SHA256}, #{SHA512}", Net::Hippie::ContentDigest.new(%i[sha256 sha512]).header_for(BODY)
+  end
+
+  def test_unsupported_algorithm
+    assert_raises ArgumentError do
+      Net::Hippie::ContentDigest.new([:md5])
+    end
+  end
+
+  def test_verify
+    assert Net::Hippie::ContentDigest.new.verify(response_with(BODY, 'Content-Digest' => "#{SHA512}, #{SHA256}"))
+  end
+
+  def test_verify_mismatch
+    assert_raises Net::Hippie::IntegrityError do
+      Net::Hippie::ContentDigest.new.verify(response_with('{"hello": "hippie"}', 'Content-Digest' => SHA256))
+    end
+  end
+
+  def test_verify_missing_header
+    assert_raises Net::Hippie::IntegrityError do
+      Net::Hippie::ContentDigest.new.verify(response_with(BODY))
+    end
+  end
+end
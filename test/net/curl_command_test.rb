@@ -0,0 +1,14 @@
+require 'test_helper'
+
+class CurlCommandTest < Minitest::Test
+  def test_to_s
+    request = Net::HTTP::Post.new(URI.parse('https://www.example.org/api'), 'Content-Type' => 'application/json')
+    request.body = %({"name":"it's"})
+
+    result = Net::Hippie::CurlCommand.new(request).to_s
+    assert result.start_with?("curl -X POST 'https://www.example.org/api' ")
+    assert_includes result, "-H 'Content-Type: application/json'"
+    assert result.end_with?(%q(--data-raw '{"name":"it'\''s"}'))
+    refute_includes result, 'Host:'
+  end
+end
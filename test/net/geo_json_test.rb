@@ -0,0 +1,25 @@
+require 'test_helper'
+
+class GeoJsonTest < Minitest::Test
+  def test_bounding_box_member
+    subject = Net::Hippie::GeoJson.new('type' => 'FeatureCollection', 'bbox' => [0, 0, 1, 1], 'features' => [])
+
+    assert_equal 0, subject.feature_count
+    assert_equal [0, 0, 1, 1], subject.bounding_box
+  end
+
+  def test_bare_geometry
+    polygon = [[[0, 0], [4, 0], [4, 3], [0, 0]]]
+    subject = Net::Hippie::GeoJson.new('type' => 'Polygon', 'coordinates' => polygon)
+
+    assert_equal 1, subject.feature_count
+    assert_equal 'Polygon', subject.features[0].geometry_type
+    assert_equal [0, 0, 4, 3], subject.bounding_box
+  end
+
+  def test_without_positions
+    subject = Net::Hippie::GeoJson.new('type' => 'FeatureCollection', 'features' => [])
+
+    assert_nil subject.bounding_box
+  end
+end
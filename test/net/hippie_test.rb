@@ -34,4 +34,160 @@ class HippieTest < Minitest::Test
     assert_equal Net::HTTPOK, response.class
     assert_equal JSON.parse(response.body)['success'], 'true'
   end
+
+  def test_post_multipart_streaming_with_retry
+    uri = 'https://www.example.org/module/multipart'
+    WebMock.stub_request(:post, uri).to_raise(Errno::ECONNRESET).then.to_return(status: 201)
+
+    file = StringIO.new('file contents')
+    response = Net::Hippie.post_multipart_streaming(uri, { 'name' => 'hippie', 'file' => file })
+
+    assert_equal Net::HTTPCreated, response.class
+    WebMock.assert_requested(:post, uri, times: 2) { |request| request.body.include?("\r\n\r\nfile contents\r\n") }
+  end
+
+  def test_post_chunked_is_not_retried
+    uri = 'https://www.example.org/module/chunked'
+    WebMock.stub_request(:post, uri).to_raise(Errno::ECONNRESET).then.to_return(status: 201)
+
+    chunks = %w[a b c]
+    assert_raises Errno::ECONNRESET do
+      Net::Hippie.post_chunked(uri) { chunks.shift }
+    end
+    WebMock.assert_requested(:post, uri, times: 1)
+  end
+
+  def test_get_with_block
+    uri = 'https://www.example.org/module/block'
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: {}.to_json)
+
+    yielded = nil
+    Net::Hippie.get(uri) do |_request, response|
+      yielded = response
+      nil
+    end
+    assert_equal Net::HTTPOK, yielded.class
+  end
+
+  def test_cookies
+    assert_equal 'session=abc123; theme=dark', Net::Hippie.cookies(session: 'abc123', theme: 'dark')
+  end
+
+  def test_cookies_rejects_invalid_values
+    assert_raises ArgumentError do
+      Net::Hippie.cookies('session' => 'abc;123')
+    end
+    assert_raises ArgumentError do
+      Net::Hippie.cookies('session' => 'café')
+    end
+    assert_raises ArgumentError do
+      Net::Hippie.cookies('bad name' => 'value')
+    end
+  end
+
+  def test_get_with_cookies
+    uri = 'https://www.example.org/'
+    WebMock.stub_request(:get, uri)
+      .with(headers: { 'Cookie' => 'session=abc123; theme=dark' })
+      .to_return(status: 200, body: {}.to_json)
+
+    response = Net::Hippie.get(uri, headers: { 'Cookie' => Net::Hippie.cookies(session: 'abc123', theme: 'dark') })
+    assert_equal Net::HTTPOK, response.class
+  end
+
+  def test_forwarded_headers
+    headers = Net::Hippie.forwarded_headers('203.0.113.1', 'https://www.example.org/')
+    assert_equal({
+      'X-Forwarded-For' => '203.0.113.1',
+      'X-Forwarded-Host' => 'www.example.org',
+      'X-Forwarded-Proto' => 'https',
+      'Forwarded' => 'for=203.0.113.1;host=www.example.org;proto=https'
+    }, headers)
+  end
+
+  def test_forwarded_headers_with_chain
+    headers = Net::Hippie.forwarded_headers(['203.0.113.1', '2001:db8:cafe::17'], 'http://www.example.org/')
+    assert_equal '203.0.113.1, 2001:db8:cafe::17', headers['X-Forwarded-For']
+    assert_equal 'for=203.0.113.1;host=www.example.org;proto=http, for="[2001:db8:cafe::17]"', headers['Forwarded']
+  end
+
+  def test_forwarded_headers_without_addresses
+    assert_equal({}, Net::Hippie.forwarded_headers([], 'https://www.example.org/'))
+    assert_equal({}, Net::Hippie.forwarded_headers(nil, 'https://www.example.org/'))
+  end
+
+  def test_trace_context
+    headers = Net::Hippie.trace_context(
+      trace_id: '4bf92f3577b34da6a3ce929d0e0e4736',
+      parent_id: '00f067aa0ba902b7',
+      flags: '01',
+      tracestate: 'congo=t61rcWkgMzE'
+    )
+    assert_equal '00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01', headers['traceparent']
+    assert_equal 'congo=t61rcWkgMzE', headers['tracestate']
+  end
+
+  def test_trace_context_rejects_invalid_ids
+    assert_raises ArgumentError do
+      Net::Hippie.trace_context(trace_id: 'abc', parent_id: '00f067aa0ba902b7', flags: '01')
+    end
+    assert_raises ArgumentError do
+      Net::Hippie.trace_context(trace_id: '4bf92f3577b34da6a3ce929d0e0e4736', parent_id: 'xyz', flags: '01')
+    end
+    assert_raises ArgumentError do
+      Net::Hippie.trace_context(trace_id: '4bf92f3577b34da6a3ce929d0e0e4736', parent_id: '00f067aa0ba902b7', flags: '1')
+    end
+  end
+
+  def test_resolve_url
+    base = 'http://a/b/c/d;p?q'
+    {
+      'g' => 'http://a/b/c/g',
+      './g' => 'http://a/b/c/g',
+      'g/' => 'http://a/b/c/g/',
+      '/g' => 'http://a/g',
+      '//g' => 'http://g',
+      '?y' => 'http://a/b/c/d;p?y',
+      'g?y' => 'http://a/b/c/g?y',
+      '#s' => 'http://a/b/c/d;p?q#s',
+      'g#s' => 'http://a/b/c/g#s',
+      ';x' => 'http://a/b/c/;x',
+      '' => 'http://a/b/c/d;p?q',
+      '.' => 'http://a/b/c/',
+      '..' => 'http://a/b/',
+      '../g' => 'http://a/b/g',
+      '../../g' => 'http://a/g',
+      '../../../g' => 'http://a/g',
+      'https://www.example.org/' => 'https://www.example.org/'
+    }.each do |relative, expected|
+      assert_equal expected, Net::Hippie.resolve_url(base, relative), relative
+    end
+  end
+
+  def test_normalize_url
+    assert_equal 'https://www.example.org/a%20b?q=caf%C3%A9', Net::Hippie.normalize_url('HTTPS://WWW.Example.ORG/a b?q=café')
+    assert_equal 'https://www.example.org/a%20b', Net::Hippie.normalize_url('https://www.example.org/a%20b')
+  end
+
+  def test_parse_www_authenticate
+    response = Net::HTTPUnauthorized.new('1.1', '401', 'Unauthorized')
+    response.add_field('WWW-Authenticate', 'Basic realm="simple", Bearer error="invalid_token"')
+    response.add_field('WWW-Authenticate', 'Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS"')
+
+    expected = [
+      { scheme: 'Basic', params: { 'realm' => 'simple' } },
+      { scheme: 'Bearer', params: { 'error' => 'invalid_token' } },
+      {
+        scheme: 'Digest',
+        params: {
+          'realm' => 'http-auth@example.org',
+          'qop' => 'auth, auth-int',
+          'algorithm' => 'SHA-256',
+          'nonce' => '7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v',
+          'opaque' => 'FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS'
+        }
+      }
+    ]
+    assert_equal expected, Net::Hippie.parse_www_authenticate(response)
+  end
 end
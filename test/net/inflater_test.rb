@@ -0,0 +1,38 @@
+require 'test_helper'
+
+class InflaterTest < Minitest::Test
+  def test_stream_gzip
+    io = StringIO.new
+    body = ('hippie ' * 20_000).freeze
+
+    written = Net::Hippie::Inflater.new.stream(gzip(body), io)
+
+    assert_equal body.bytesize, written
+    assert_equal body, io.string
+  end
+
+  def test_stream_deflate
+    io = StringIO.new
+
+    written = Net::Hippie::Inflater.new('deflate').stream(Zlib::Deflate.deflate('hippie'), io)
+
+    assert_equal 6, written
+    assert_equal 'hippie', io.string
+  end
+
+  def test_unsupported_encoding
+    assert_raises ArgumentError do
+      Net::Hippie::Inflater.new('br')
+    end
+  end
+
+  private
+
+  def gzip(text)
+    io = StringIO.new
+    writer = Zlib::GzipWriter.new(io)
+    writer.write(text)
+    writer.close
+    io.string
+  end
+end
@@ -0,0 +1,56 @@
+require 'test_helper'
+
+class JsonPatchTest < Minitest::Test
+  def test_apply
+    document = { 'baz' => 'qux', 'foo' => 'bar', 'list' => %w[a c], 'nested' => { 'a/b' => 1, 'm~n' => 2 } }
+    operations = [
+      { op: 'replace', path: '/baz', value: 'boo' },
+      { op: 'add', path: '/hello', value: ['world'] },
+      { op: 'remove', path: '/foo' },
+      { op: 'add', path: '/list/1', value: 'b' },
+      { op: 'add', path: '/list/-', value: 'd' },
+      { op: 'move', path: '/moved', from: '/nested/a~1b' },
+      { op: 'copy', path: '/copied', from: '/nested/m~0n' },
+      { op: 'test', path: '/list', value: %w[a b c d] }
+    ]
+
+    expected = {
+      'baz' => 'boo',
+      'hello' => ['world'],
+      'list' => %w[a b c d],
+      'nested' => { 'm~n' => 2 },
+      'moved' => 1,
+      'copied' => 2
+    }
+    assert_equal expected, Net::Hippie.apply_json_patch(document, operations)
+    assert_equal 'bar', document['foo']
+  end
+
+  def test_replace_document
+    assert_equal [1], Net::Hippie.apply_json_patch({ 'a' => 1 }, [{ 'op' => 'replace', 'path' => '', 'value' => [1] }])
+  end
+
+  def test_failed_test_operation
+    assert_raises Net::Hippie::JsonPatchError do
+      Net::Hippie.apply_json_patch({ 'a' => 1 }, [{ op: 'test', path: '/a', value: 2 }])
+    end
+  end
+
+  def test_missing_member
+    assert_raises Net::Hippie::JsonPatchError do
+      Net::Hippie.apply_json_patch({ 'a' => 1 }, [{ op: 'remove', path: '/b' }])
+    end
+  end
+
+  def test_index_out_of_bounds
+    assert_raises Net::Hippie::JsonPatchError do
+      Net::Hippie.apply_json_patch({ 'a' => [1] }, [{ op: 'add', path: '/a/2', value: 3 }])
+    end
+  end
+
+  def test_unknown_operation
+    assert_raises Net::Hippie::JsonPatchError do
+      Net::Hippie.apply_json_patch({}, [{ op: 'merge', path: '/a' }])
+    end
+  end
+end
@@ -0,0 +1,67 @@
+require 'test_helper'
+
+class MultipartBodyTest < Minitest::Test
+  def test_read
+    file = StringIO.new('file contents')
+    subject = Net::Hippie::MultipartBody.new(
+      [['name', 'hippie'], ['file', file, { filename: 'hippie.txt', content_type: 'text/plain' }]],
+      boundary: 'xyz'
+    )
+
+    expected = [
+      '--xyz',
+      'Content-Disposition: form-data; name="name"',
+      '',
+      'hippie',
+      '--xyz',
+      'Content-Disposition: form-data; name="file"; filename="hippie.txt"',
+      'Content-Type: text/plain',
+      '',
+      'file contents',
+      '--xyz--',
+      ''
+    ].join("\r\n")
+    assert_equal 'multipart/form-data; boundary=xyz', subject.content_type
+    assert_equal expected.bytesize, subject.size
+    assert_equal expected, subject.read
+  end
+
+  def test_read_in_pieces
+    subject = Net::Hippie::MultipartBody.new({ 'file' => StringIO.new('x' * 100) }, boundary: 'xyz')
+    size = subject.size
+
+    pieces = []
+    while (piece = subject.read(16))
+      pieces << piece
+    end
+
+    assert pieces.all? { |x| x.bytesize <= 16 }
+    assert_equal size, pieces.join.bytesize
+  end
+
+  def test_rewind
+    file = StringIO.new('file contents')
+    file.read
+    subject = Net::Hippie::MultipartBody.new({ 'file' => file }, boundary: 'xyz')
+
+    body = subject.read
+    assert_includes body, 'file contents'
+
+    subject.rewind
+    assert_equal body, subject.read
+  end
+
+  def test_size_of_unsized_io
+    reader, writer = IO.pipe
+    writer.close
+
+    assert_nil Net::Hippie::MultipartBody.new({ 'file' => reader }).size
+  ensure
+    reader&.close
+  end
+
+  def test_content_type_for
+    assert_equal 'image/png', Net::Hippie::MultipartBody.content_type_for('/tmp/hippie.PNG')
+    assert_equal 'application/octet-stream', Net::Hippie::MultipartBody.content_type_for('/tmp/hippie')
+  end
+end
@@ -0,0 +1,54 @@
+require 'test_helper'
+
+class MultipartMixedTest < Minitest::Test
+  def test_responses
+    body = [
+      '--batch_xyz',
+      'Content-Type: application/http',
+      'Content-ID: response-1',
+      '',
+      'HTTP/1.1 200 OK',
+      'Content-Type: application/json',
+      'Content-Length: 15',
+      '',
+      '{"id":"hippie"}',
+      '--batch_xyz',
+      'Content-Type: application/http',
+      'Content-ID: response-2',
+      '',
+      'HTTP/1.1 404 Not Found',
+      'Content-Length: 0',
+      '',
+      '',
+      '--batch_xyz',
+      'Content-Type: application/http',
+      'Content-ID: response-3',
+      '',
+      'HTTP/1.1 200 OK',
+      'Content-Type: text/plain',
+      '',
+      'done',
+      '--batch_xyz',
+      'Content-Type: application/http',
+      '',
+      'garbage',
+      '--batch_xyz--',
+      ''
+    ].join("\r\n")
+
+    responses = Net::Hippie.parse_multipart_mixed(response_with(body, 'Content-Type' => 'multipart/mixed; boundary=batch_xyz'))
+
+    assert_equal %w[200 404 200 0], responses.map(&:code)
+    assert_equal '{"id":"hippie"}', responses[0].body
+    assert_equal 'application/json', responses[0].content_type
+    assert_equal 'Not Found', responses[1].message
+    assert_equal '', responses[1].body
+    assert_equal 'done', responses[2].body
+    assert_equal 'text/plain', responses[2]['Content-Type']
+    assert_includes responses[3].body, 'garbage'
+  end
+
+  def test_without_boundary
+    assert_empty Net::Hippie.parse_multipart_mixed(response_with('', 'Content-Type' => 'multipart/mixed'))
+  end
+end
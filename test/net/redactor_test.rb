@@ -0,0 +1,43 @@
+require 'test_helper'
+
+class RedactorTest < Minitest::Test
+  attr_reader :subject
+
+  def initialize(*args)
+    super
+    @subject = Net::Hippie::Redactor.new(query_params: ['access_token'])
+  end
+
+  def test_redact_headers
+    headers = { 'Authorization' => 'Bearer secret', 'Accept' => 'application/json' }
+    result = subject.redact_headers(headers)
+    assert_equal({ 'Authorization' => '[REDACTED]', 'Accept' => 'application/json' }, result)
+  end
+
+  def test_redact_url
+    result = subject.redact_url('https://www.example.org/?page=1&access_token=secret')
+    assert_equal 'https://www.example.org/?page=1&access_token=[REDACTED]', result
+  end
+
+  def test_redact_debug_output
+    output = StringIO.new
+    message = "GET /?access_token=secret HTTP/1.1\r\nAccept: */*\r\nAuthorization: Bearer secret\r\nCookie: session=secret\r\n\r\n".dump
+    subject.wrap(output) << message
+    refute_includes output.string, 'secret'
+    assert_includes output.string, 'Authorization: [REDACTED]\r\n'
+    assert_includes output.string, 'Cookie: [REDACTED]\r\n'
+    assert_includes output.string, 'Accept: */*\r\n'
+  end
+
+  def test_redact_response_header_line
+    output = StringIO.new
+    subject.wrap(output) << %(-> "Set-Cookie: session=secret; HttpOnly\\r\\n"\n)
+    assert_equal %(-> "Set-Cookie: [REDACTED]\\r\\n"\n), output.string
+  end
+
+  def test_custom_headers
+    subject = Net::Hippie::Redactor.new(headers: ['X-Secret'])
+    assert_equal 'X-Secret: [REDACTED]', subject.redact('X-Secret: shhh')
+    assert_equal 'Authorization: Bearer token', subject.redact('Authorization: Bearer token')
+  end
+end
@@ -0,0 +1,158 @@
+require 'test_helper'
+require 'tmpdir'
+
+class ResponsesTest < Minitest::Test
+  def test_pretty_print_headers
+    response = response_with('{"name":"hippie"}', 'content-type' => 'application/json', 'x-request-id' => 'abc123')
+
+    expected = [
+      'HTTP/1.1 200 OK',
+      'Content-Type: application/json',
+      'X-Request-Id: abc123',
+      '',
+      '{"name":"hippie"}'
+    ].join("\n")
+    assert_equal expected, Net::Hippie.pretty_print_headers(response)
+  end
+
+  def test_pretty_print_headers_truncates_the_body
+    output = Net::Hippie.pretty_print_headers(response_with('a' * 300))
+    assert output.end_with?("\n\n#{'a' * 200}")
+  end
+
+  def test_inspect_response
+    response = response_with('{"name":"hippie"}', 'Content-Type' => 'application/json')
+    assert_equal '#<Net::HTTPOK 200 OK application/json 17 bytes>', Net::Hippie.inspect_response(response)
+  end
+
+  def test_assert_content_type
+    response = response_with('{}', 'Content-Type' => 'Application/JSON; charset=utf-8')
+    assert_same response, Net::Hippie.assert_content_type(response, 'application/json')
+  end
+
+  def test_assert_content_type_mismatch
+    response = response_with('<html></html>', 'Content-Type' => 'text/html')
+
+    error = assert_raises Net::Hippie::UnexpectedContentTypeError do
+      Net::Hippie.assert_content_type(response, 'application/json')
+    end
+    assert_equal 'application/json', error.expected
+    assert_equal 'text/html', error.actual
+    assert_includes error.message, 'application/json'
+    assert_includes error.message, 'text/html'
+  end
+
+  def test_decompress_and_stream
+    uri = 'https://www.example.org/logs/app.log.gz'
+    compressed = StringIO.new.tap do |x|
+      writer = Zlib::GzipWriter.new(x)
+      writer.write('a large log file')
+      writer.close
+    end.string
+    WebMock.stub_request(:get, uri).to_return(status: 200, body: compressed, headers: { 'Content-Type' => 'application/gzip' })
+    io = StringIO.new
+
+    written = Net::Hippie.decompress_and_stream(Net::Hippie::Client.new.get(uri), io)
+
+    assert_equal 16, written
+    assert_equal 'a large log file', io.string
+  end
+
+  def test_media_type_predicates
+    {
+      'application/json; charset=utf-8' => %i[json?],
+      'application/problem+json' => %i[json?],
+      'text/json' => %i[json? text?],
+      'application/xml' => %i[xml?],
+      'application/atom+xml' => %i[xml?],
+      'text/xml' => %i[xml? text?],
+      'text/html' => %i[html? text?],
+      'text/plain' => %i[text?],
+      'image/png' => %i[binary?],
+      'video/mp4' => %i[binary?],
+      'application/octet-stream' => %i[binary?],
+      'application/x-www-form-urlencoded' => []
+    }.each do |content_type, expected|
+      response = response_with('', 'Content-Type' => content_type)
+      %i[json? xml? html? binary? text?].each do |predicate|
+        assert_equal expected.include?(predicate), Net::Hippie.public_send(predicate, response), "#{predicate} #{content_type}"
+      end
+    end
+  end
+
+  def test_encoded_bodies
+    bytes = "\x89PNG\r\n\x1A\n\xFB\xFF".b
+    response = response_with(bytes, 'Content-Type' => 'image/png')
+
+    assert_equal Base64.strict_encode64(bytes), Net::Hippie.base64_body(response)
+    assert_equal 'iVBORw0KGgr7_w', Net::Hippie.base64_body_url_safe(response)
+    assert_equal bytes.unpack1('H*'), Net::Hippie.hex_body(response)
+    assert_equal '89504e470d0a1a0afbff', Net::Hippie.hex_body(response)
+  end
+
+  def test_gzip_body_stream
+    payload = Random.new(42).bytes(5 * 1024 * 1024)
+    io = StringIO.new
+    writer = Zlib::GzipWriter.new(io)
+    writer.write(payload)
+    writer.close
+    response = response_with(io.string, 'Content-Type' => 'application/gzip')
+
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, 'out.bin')
+      written = Net::Hippie.gzip_body_stream(response, path)
+
+      assert_equal payload.bytesize, written
+      assert_equal payload.bytesize, File.size(path)
+      assert_equal OpenSSL::Digest::SHA256.hexdigest(payload), OpenSSL::Digest::SHA256.file(path).hexdigest
+    end
+  end
+
+  def test_structured_header
+    response = response_with('', 'Priority' => 'u=1, i', 'X-Flags' => '"a", "b"', 'X-Count' => '42')
+    item = Net::Hippie::StructuredFields::Item
+
+    assert_equal({ 'u' => item.new(1, {}), 'i' => item.new(true, {}) }, Net::Hippie.structured_header(response, 'priority'))
+    assert_equal [item.new('a', {}), item.new('b', {})], Net::Hippie.structured_header(response, 'X-Flags')
+    assert_equal item.new(42, {}), Net::Hippie.structured_header(response, 'X-Count')
+    assert_nil Net::Hippie.structured_header(response, 'X-Missing')
+  end
+
+  def test_structured_header_with_invalid_value
+    response = response_with('', 'Priority' => 'u=?2')
+
+    assert_raises ArgumentError do
+      Net::Hippie.structured_header(response, 'Priority')
+    end
+  end
+
+  def test_assertions_chain
+    response = response_with('{}', 'Content-Type' => 'application/json', 'X-Request-Id' => 'abc123')
+
+    result = Net::Hippie.assert_header_present(
+      Net::Hippie.assert_content_type(Net::Hippie.assert_status(response, 200), 'application/json'),
+      'x-request-id'
+    )
+    assert_same response, result
+    assert_same response, Net::Hippie.assert_header(response, 'content-type', 'APPLICATION/JSON')
+    assert_same response, Net::Hippie.assert_header_absent(response, 'Set-Cookie')
+  end
+
+  def test_assert_header_mismatch
+    response = response_with('', 'Content-Type' => 'text/html')
+
+    error = assert_raises Net::Hippie::AssertionError do
+      Net::Hippie.assert_header(response, 'content-type', 'application/json')
+    end
+    assert_equal "Expected header 'content-type' to be 'application/json' but got 'text/html'", error.message
+  end
+
+  def test_failed_assertions
+    response = response_with('', 'Set-Cookie' => 'session=abc123')
+
+    assert_raises(Net::Hippie::AssertionError) { Net::Hippie.assert_status(response, 201) }
+    assert_raises(Net::Hippie::AssertionError) { Net::Hippie.assert_header_present(response, 'X-Request-Id') }
+    assert_raises(Net::Hippie::AssertionError) { Net::Hippie.assert_header_absent(response, 'Set-Cookie') }
+    assert_raises(Net::Hippie::AssertionError) { Net::Hippie.assert_header(response, 'X-Missing', '') }
+  end
+end
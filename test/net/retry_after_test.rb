@@ -0,0 +1,35 @@
+require 'test_helper'
+
+class RetryAfterTest < Minitest::Test
+  def test_delay_in_seconds
+    response = Net::HTTPTooManyRequests.new('1.1', '429', 'Too Many Requests')
+    response['Retry-After'] = '30'
+    assert_equal 30, Net::Hippie::RetryAfter.delay_for(response)
+  end
+
+  def test_delay_as_http_date
+    now = Time.utc(2015, 10, 21, 7, 27, 30)
+    response = Net::HTTPServiceUnavailable.new('1.1', '503', 'Service Unavailable')
+    response['Retry-After'] = 'Wed, 21 Oct 2015 07:28:00 GMT'
+    assert_equal 30, Net::Hippie::RetryAfter.delay_for(response, now)
+  end
+
+  def test_delay_as_http_date_in_the_past
+    now = Time.utc(2015, 10, 21, 7, 29, 0)
+    response = Net::HTTPServiceUnavailable.new('1.1', '503', 'Service Unavailable')
+    response['Retry-After'] = 'Wed, 21 Oct 2015 07:28:00 GMT'
+    assert_equal 0, Net::Hippie::RetryAfter.delay_for(response, now)
+  end
+
+  def test_ignores_invalid_values
+    response = Net::HTTPTooManyRequests.new('1.1', '429', 'Too Many Requests')
+    response['Retry-After'] = 'soon'
+    assert_nil Net::Hippie::RetryAfter.delay_for(response)
+  end
+
+  def test_ignores_other_responses
+    response = Net::HTTPOK.new('1.1', '200', 'OK')
+    response['Retry-After'] = '30'
+    assert_nil Net::Hippie::RetryAfter.delay_for(response)
+  end
+end
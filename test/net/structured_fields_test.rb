@@ -0,0 +1,51 @@
+require 'test_helper'
+
+class StructuredFieldsTest < Minitest::Test
+  Item = Net::Hippie::StructuredFields::Item
+  InnerList = Net::Hippie::StructuredFields::InnerList
+
+  def test_priority
+    expected = { 'u' => Item.new(1, {}), 'i' => Item.new(true, {}) }
+    assert_equal expected, Net::Hippie::StructuredFields.parse('u=1, i', type: :dictionary)
+  end
+
+  def test_bare_items
+    {
+      '42' => 42,
+      '-4.5' => -4.5,
+      '"say \\"hi\\""' => 'say "hi"',
+      'text/html' => :'text/html',
+      ':aGlwcGll:' => 'hippie',
+      '?0' => false
+    }.each do |value, expected|
+      assert_equal expected, Net::Hippie::StructuredFields.parse(value).value, value
+    end
+  end
+
+  def test_list_with_inner_list_and_params
+    expected = [
+      Item.new(:sugar, {}),
+      InnerList.new([Item.new('foo', {}), Item.new(:bar, { 'q' => 0.5 })], { 'lvl' => 1 }),
+      Item.new(:tea, { 'a' => true })
+    ]
+    assert_equal expected, Net::Hippie::StructuredFields.parse('sugar, ("foo" bar;q=0.5);lvl=1, tea;a', type: :list)
+  end
+
+  def test_cdn_cache_control
+    expected = { 'max-age' => Item.new(3600, {}), 'must-revalidate' => Item.new(true, {}) }
+    assert_equal expected, Net::Hippie::StructuredFields.parse('max-age=3600, must-revalidate', type: :dictionary)
+  end
+
+  def test_empty_list
+    assert_equal [], Net::Hippie::StructuredFields.parse('', type: :list)
+  end
+
+  def test_invalid
+    ['', '1,', 'a,,b', '1234567890123456', '1.2345', '"unterminated', 'a=1,', ':not base64:'].each do |value|
+      assert_raises ArgumentError, value do
+        type = value.include?('=') ? :dictionary : :list
+        Net::Hippie::StructuredFields.parse(value, type: value.empty? ? :item : type)
+      end
+    end
+  end
+end
@@ -0,0 +1,38 @@
+require 'test_helper'
+
+class TextDecoderTest < Minitest::Test
+  attr_reader :subject
+
+  def initialize(*args)
+    super
+    @subject = Net::Hippie::TextDecoder.new
+  end
+
+  def test_strips_utf8_bom
+    result = subject.decode("\xEF\xBB\xBF{\"name\":\"café\"}".b)
+    assert_equal '{"name":"café"}', result
+    assert_equal Encoding::UTF_8, result.encoding
+  end
+
+  def test_converts_utf16le_with_bom
+    result = subject.decode("\xFF\xFE".b + '{"name":"café"}'.encode('UTF-16LE').b)
+    assert_equal '{"name":"café"}', result
+    assert result.valid_encoding?
+  end
+
+  def test_converts_utf16be_with_bom
+    result = subject.decode("\xFE\xFF".b + '{"name":"café"}'.encode('UTF-16BE').b)
+    assert_equal '{"name":"café"}', result
+  end
+
+  def test_uses_charset_without_bom
+    result = subject.decode('café'.encode('ISO-8859-1').b, 'iso-8859-1')
+    assert_equal 'café', result
+  end
+
+  def test_replaces_invalid_utf8
+    result = subject.decode("caf\xC3\x28 \xFF".b, 'utf-8')
+    assert_equal "caf\uFFFD( \uFFFD", result
+    assert result.valid_encoding?
+  end
+end
@@ -0,0 +1,48 @@
+require 'test_helper'
+
+class VAPIDBuilderTest < Minitest::Test
+  def test_build_authorization_header
+    key = OpenSSL::PKey::EC.generate('prime256v1')
+    subject = Net::Hippie::VAPIDBuilder.new(
+      subject: 'mailto:hippie@example.org',
+      public_key_pem: public_pem_for(key),
+      private_key_pem: key.to_pem
+    )
+
+    header = subject.build_authorization_header(audience: 'https://push.example.net', expiration: 1_453_523_768)
+
+    match = header.match(/\Avapid t=([\w-]+\.[\w-]+\.[\w-]+), k=([\w-]+)\z/)
+    refute_nil match
+    header_part, claims_part, signature_part = match[1].split('.')
+    assert_equal({ 'typ' => 'JWT', 'alg' => 'ES256' }, JSON.parse(Base64.urlsafe_decode64(pad(header_part))))
+    assert_equal(
+      { 'aud' => 'https://push.example.net', 'exp' => 1_453_523_768, 'sub' => 'mailto:hippie@example.org' },
+      JSON.parse(Base64.urlsafe_decode64(pad(claims_part)))
+    )
+    assert_equal 64, Base64.urlsafe_decode64(pad(signature_part)).bytesize
+    assert_equal key.public_key.to_bn.to_s(2), Base64.urlsafe_decode64(pad(match[2]))
+  end
+
+  def test_default_expiration
+    key = OpenSSL::PKey::EC.generate('prime256v1')
+    subject = Net::Hippie::VAPIDBuilder.new(subject: 'mailto:hippie@example.org', public_key_pem: public_pem_for(key), private_key_pem: key.to_pem)
+
+    jwt = subject.build_authorization_header(audience: 'https://push.example.net')[/t=([^,]+)/, 1]
+    claims = JSON.parse(Base64.urlsafe_decode64(pad(jwt.split('.')[1])))
+    assert_in_delta Time.now.to_i + (12 * 60 * 60), claims['exp'], 5
+  end
+
+  private
+
+  def public_pem_for(key)
+    return key.public_to_pem if key.respond_to?(:public_to_pem)
+
+    public_key = OpenSSL::PKey::EC.new(key.group)
+    public_key.public_key = key.public_key
+    public_key.to_pem
+  end
+
+  def pad(value)
+    value + ('=' * (-value.length % 4))
+  end
+end
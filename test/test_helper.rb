@@ -7,6 +7,18 @@ require 'securerandom'
 require 'vcr'
 require 'webmock'
 
+module ResponseHelper
+  def response_with(body, headers = {})
+    Net::HTTPOK.new('1.1', '200', 'OK').tap do |response|
+      headers.each { |name, value| response[name] = value }
+      response.instance_variable_set(:@read, true)
+      response.body = body
+    end
+  end
+end
+
+Minitest::Test.include(ResponseHelper)
+
 Net::Hippie.logger = ENV['CIBUILD'] ? Logger.new('/dev/null') : Logger.new(STDERR)
 
 VCR.configure do |config|